@@ -0,0 +1,142 @@
+use crate::geometry::Vec2;
+use rand::Rng;
+
+/// Where a shot fired from `from` at `projectile_speed` meets a target at
+/// `target` moving at `target_vel`, with how many seconds that takes.
+/// `None` when the shot can never catch up.
+pub fn intercept(
+    from: Vec2,
+    target: Vec2,
+    target_vel: Vec2,
+    projectile_speed: f32,
+) -> Option<(Vec2, f32)> {
+    // |offset + target_vel * t| = projectile_speed * t, solved for the first t > 0
+    let offset = (target.0 - from.0, target.1 - from.1);
+    let a = target_vel.0 * target_vel.0 + target_vel.1 * target_vel.1
+        - projectile_speed * projectile_speed;
+    let b = 2.0 * (offset.0 * target_vel.0 + offset.1 * target_vel.1);
+    let c = offset.0 * offset.0 + offset.1 * offset.1;
+    let time = if a.abs() < f32::EPSILON {
+        // as fast as the shot, only works when it is coming closer
+        (b < 0.0).then(|| -c / b)?
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let (t1, t2) = ((-b - root) / (2.0 * a), (-b + root) / (2.0 * a));
+        match (t1 > 0.0, t2 > 0.0) {
+            (true, true) => t1.min(t2),
+            (true, false) => t1,
+            (false, true) => t2,
+            (false, false) => return None,
+        }
+    };
+    let point = (
+        target.0 + target_vel.0 * time,
+        target.1 + target_vel.1 * time,
+    );
+    Some((point, time))
+}
+
+/// The point to fire at to hit a moving target, aiming where it will be
+/// rather than where it is. The target wraps around the screen like
+/// `wrap_position` with `margin`, so its copies one screen over are tried
+/// too; shots don't wrap, so only meeting points on screen count.
+/// Falls back to the target itself when nothing can catch it.
+pub fn lead_target(
+    from: Vec2,
+    target: Vec2,
+    target_vel: Vec2,
+    projectile_speed: f32,
+    viewport: (u32, u32),
+    margin: f32,
+) -> Vec2 {
+    let (w, h) = (viewport.0 as f32, viewport.1 as f32);
+    let (span_x, span_y) = (w + 2.0 * margin, h + 2.0 * margin);
+    let on_screen = |p: Vec2| (0.0..=w).contains(&p.0) && (0.0..=h).contains(&p.1);
+    let mut best: Option<(Vec2, f32)> = None;
+    for dx in [-1.0, 0.0, 1.0] {
+        for dy in [-1.0, 0.0, 1.0] {
+            let image = (target.0 + dx * span_x, target.1 + dy * span_y);
+            let Some((point, time)) = intercept(from, image, target_vel, projectile_speed) else {
+                continue;
+            };
+            if on_screen(point) && best.is_none_or(|(_, best_time)| time < best_time) {
+                best = Some((point, time));
+            }
+        }
+    }
+    best.map_or(target, |(point, _)| point)
+}
+
+/// Angle a shot from `from` needs to head for `to`, 0 rad = up like `heading`.
+pub fn aim_angle(from: Vec2, to: Vec2) -> f32 {
+    (to.0 - from.0).atan2(from.1 - to.1)
+}
+
+/// Random miss of up to `max_error` radians either way, no roll at all when 0.
+pub fn aim_error(rng: &mut impl Rng, max_error: f32) -> f32 {
+    if max_error > 0.0 {
+        rng.gen_range(-max_error..=max_error)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Vec2, b: Vec2) -> bool {
+        (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3
+    }
+
+    #[test]
+    fn intercept_meets_a_moving_target() {
+        // target 100 px right, moving up at 30, shot at 50: meets at t = 2.5
+        let (point, time) = intercept((0.0, 0.0), (100.0, 0.0), (0.0, -30.0), 50.0).unwrap();
+        assert!((time - 2.5).abs() < 1e-4, "{}", time);
+        assert!(close(point, (100.0, -75.0)), "{:?}", point);
+    }
+
+    #[test]
+    fn intercept_gives_up_on_targets_it_cant_catch() {
+        // running away faster than the shot
+        assert_eq!(intercept((0.0, 0.0), (100.0, 0.0), (80.0, 0.0), 50.0), None);
+        // exactly as fast and moving away
+        assert_eq!(intercept((0.0, 0.0), (100.0, 0.0), (50.0, 0.0), 50.0), None);
+        // exactly as fast but coming closer
+        let (point, _) = intercept((0.0, 0.0), (100.0, 0.0), (-50.0, 0.0), 50.0).unwrap();
+        assert!(close(point, (50.0, 0.0)), "{:?}", point);
+    }
+
+    #[test]
+    fn lead_target_takes_the_short_way_across_the_wrap() {
+        // target near the bottom heading down, it reappears at the top right
+        // above the shooter long before a shot could chase it down
+        let point = lead_target(
+            (400.0, 100.0),
+            (400.0, 590.0),
+            (0.0, 50.0),
+            200.0,
+            (800, 600),
+            0.0,
+        );
+        assert!(close(point, (400.0, 12.0)), "{:?}", point);
+    }
+
+    #[test]
+    fn lead_target_falls_back_to_the_target() {
+        let target = (400.0, 300.0);
+        let point = lead_target((0.0, 0.0), target, (500.0, 0.0), 100.0, (800, 600), 0.0);
+        assert_eq!(point, target);
+    }
+
+    #[test]
+    fn aim_angle_is_zero_straight_up() {
+        assert!(aim_angle((0.0, 0.0), (0.0, -10.0)).abs() < 1e-6);
+        assert!((aim_angle((0.0, 0.0), (10.0, 0.0)) - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::config::AsteroidConfig;
+use crate::geometry::{outline_radius, rotate, scale_outline, translate, Vec2};
+use crate::procgen::{generate_outline, OutlineParams};
+use rand::Rng;
+use std::f32::consts::TAU;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AsteroidSize {
+    Large,
+    Medium,
+    Small,
+}
+
+impl AsteroidSize {
+    pub fn next(self) -> Option<Self> {
+        match self {
+            AsteroidSize::Large => Some(AsteroidSize::Medium),
+            AsteroidSize::Medium => Some(AsteroidSize::Small),
+            AsteroidSize::Small => None,
+        }
+    }
+}
+
+pub struct Asteroid {
+    pub pos: Vec2,
+    pub vel: Vec2, // px per second
+    pub angle: f32,
+    pub prev_pos: Vec2,
+    pub prev_angle: f32,
+    pub shape: Vec<Vec2>,
+    pub radius: f32,
+    pub size: AsteroidSize,
+    pub mass: f32,
+    /// Unique within a world, handed out by the world as asteroids appear.
+    pub id: u32,
+}
+
+impl Asteroid {
+    /// Outline rotated and moved to where the asteroid is now.
+    pub fn world_outline(&self) -> Vec<Vec2> {
+        translate(&rotate(&self.shape, self.angle), self.pos)
+    }
+}
+
+/// Where new asteroid outlines come from.
+pub enum ShapeSource<'a> {
+    /// Pick one of the loaded outlines.
+    Outlines(&'a [Vec<Vec2>]),
+    /// Generate a fresh outline every time.
+    Generated(&'a OutlineParams),
+}
+
+impl ShapeSource<'_> {
+    pub fn pick(&self, rng: &mut impl Rng) -> Vec<Vec2> {
+        match self {
+            ShapeSource::Outlines(shapes) => shapes[rng.gen_range(0..shapes.len())].clone(),
+            ShapeSource::Generated(params) => generate_outline(params, rng.gen()),
+        }
+    }
+}
+
+pub fn spawn_asteroid(
+    base_shape: &[Vec2],
+    size: AsteroidSize,
+    pos: Vec2,
+    vel: Vec2,
+    angle: f32,
+    config: &AsteroidConfig,
+    rng: &mut impl Rng,
+) -> Asteroid {
+    let rules = config.size(size);
+    let scale = rng.gen_range(rules.scale_range.clone());
+    let shape = scale_outline(base_shape, scale);
+    let radius = outline_radius(&shape);
+    Asteroid {
+        pos,
+        vel,
+        angle,
+        prev_pos: pos,
+        prev_angle: angle,
+        shape,
+        radius,
+        size,
+        mass: rules.mass * scale * scale,
+        id: 0,
+    }
+}
+
+/// Elastic bounce between two touching asteroids, momentum is conserved.
+/// Overlap is pushed apart by at most `max_push` so fresh fragments drift apart
+/// instead of popping.
+pub fn resolve_bounce(a: &mut Asteroid, b: &mut Asteroid, max_push: f32) {
+    let dx = b.pos.0 - a.pos.0;
+    let dy = b.pos.1 - a.pos.1;
+    let dist = (dx * dx + dy * dy).sqrt();
+    let normal = if dist > f32::EPSILON {
+        (dx / dist, dy / dist)
+    } else {
+        (1.0, 0.0)
+    };
+    let inv_a = 1.0 / a.mass;
+    let inv_b = 1.0 / b.mass;
+
+    // only bounce when moving towards each other
+    let closing = (b.vel.0 - a.vel.0) * normal.0 + (b.vel.1 - a.vel.1) * normal.1;
+    if closing < 0.0 {
+        let impulse = -2.0 * closing / (inv_a + inv_b);
+        a.vel.0 -= impulse * inv_a * normal.0;
+        a.vel.1 -= impulse * inv_a * normal.1;
+        b.vel.0 += impulse * inv_b * normal.0;
+        b.vel.1 += impulse * inv_b * normal.1;
+    }
+
+    // separation, lighter asteroid moves more
+    let push = (a.radius + b.radius - dist).clamp(0.0, max_push);
+    let share_a = inv_a / (inv_a + inv_b);
+    a.pos.0 -= normal.0 * push * share_a;
+    a.pos.1 -= normal.1 * push * share_a;
+    b.pos.0 += normal.0 * push * (1.0 - share_a);
+    b.pos.1 += normal.1 * push * (1.0 - share_a);
+}
+
+/// Bounce an asteroid off a shield of `radius` around `center`, the shield
+/// doesn't budge. Returns whether they were touching.
+pub fn deflect_asteroid(
+    asteroid: &mut Asteroid,
+    center: Vec2,
+    center_vel: Vec2,
+    radius: f32,
+) -> bool {
+    let dx = asteroid.pos.0 - center.0;
+    let dy = asteroid.pos.1 - center.1;
+    let dist = (dx * dx + dy * dy).sqrt();
+    let reach = radius + asteroid.radius;
+    if dist >= reach {
+        return false;
+    }
+    let normal = if dist > f32::EPSILON {
+        (dx / dist, dy / dist)
+    } else {
+        (0.0, -1.0)
+    };
+
+    // mirror the velocity relative to the ship when moving inwards
+    let rel = (asteroid.vel.0 - center_vel.0, asteroid.vel.1 - center_vel.1);
+    let closing = rel.0 * normal.0 + rel.1 * normal.1;
+    if closing < 0.0 {
+        asteroid.vel.0 -= 2.0 * closing * normal.0;
+        asteroid.vel.1 -= 2.0 * closing * normal.1;
+    }
+
+    // and put it back outside the shield
+    asteroid.pos.0 = center.0 + normal.0 * reach;
+    asteroid.pos.1 = center.1 + normal.1 * reach;
+    true
+}
+
+pub fn split_asteroid(
+    asteroid: &Asteroid,
+    rng: &mut impl Rng,
+    shapes: &ShapeSource,
+    config: &AsteroidConfig,
+) -> Vec<Asteroid> {
+    let mut pieces = Vec::new();
+    if let Some(next_size) = asteroid.size.next() {
+        for _ in 0..config.size(asteroid.size).children {
+            let base = shapes.pick(rng);
+            let vel = pick_random_velocity(rng, config.size(next_size).speed_range.clone());
+            let angle = rng.gen_range(0.0..TAU);
+            pieces.push(spawn_asteroid(
+                &base,
+                next_size,
+                asteroid.pos,
+                vel,
+                angle,
+                config,
+                rng,
+            ));
+        }
+    }
+    pieces
+}
+
+pub fn pick_spawn_point(rng: &mut impl Rng, viewport: (u32, u32), margin: f32) -> Vec2 {
+    let (w, h) = (viewport.0 as f32, viewport.1 as f32);
+    match rng.gen_range(0..4) {
+        0 => (-margin, rng.gen_range(0.0..h)),
+        1 => (w + margin, rng.gen_range(0.0..h)),
+        2 => (rng.gen_range(0.0..w), -margin),
+        _ => (rng.gen_range(0.0..w), h + margin),
+    }
+}
+
+pub fn pick_random_velocity(rng: &mut impl Rng, speed_range: std::ops::Range<f32>) -> Vec2 {
+    let angle = rng.gen_range(0.0..TAU);
+    let speed = rng.gen_range(speed_range);
+    let (sin, cos) = angle.sin_cos();
+    (speed * sin, -speed * cos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rock(pos: Vec2, vel: Vec2, mass: f32) -> Asteroid {
+        Asteroid {
+            pos,
+            vel,
+            angle: 0.0,
+            prev_pos: pos,
+            prev_angle: 0.0,
+            shape: Vec::new(),
+            radius: 10.0,
+            size: AsteroidSize::Medium,
+            mass,
+            id: 0,
+        }
+    }
+
+    fn momentum(a: &Asteroid, b: &Asteroid) -> Vec2 {
+        (
+            a.mass * a.vel.0 + b.mass * b.vel.0,
+            a.mass * a.vel.1 + b.mass * b.vel.1,
+        )
+    }
+
+    #[test]
+    fn bounce_keeps_momentum() {
+        let mut a = rock((0.0, 0.0), (50.0, 0.0), 4.0);
+        let mut b = rock((15.0, 5.0), (-30.0, 10.0), 1.0);
+        let before = momentum(&a, &b);
+        resolve_bounce(&mut a, &mut b, 100.0);
+        let after = momentum(&a, &b);
+        assert!((before.0 - after.0).abs() < 1e-3 && (before.1 - after.1).abs() < 1e-3);
+        // and they are heading apart now
+        assert!(b.vel.0 > a.vel.0);
+    }
+
+    #[test]
+    fn a_pair_moving_apart_keeps_its_velocities() {
+        let mut a = rock((0.0, 0.0), (-10.0, 0.0), 1.0);
+        let mut b = rock((15.0, 0.0), (10.0, 0.0), 1.0);
+        resolve_bounce(&mut a, &mut b, 100.0);
+        assert_eq!((a.vel, b.vel), ((-10.0, 0.0), (10.0, 0.0)));
+    }
+
+    #[test]
+    fn separation_is_capped_and_the_lighter_one_moves_more() {
+        // 5 px of overlap, only 1 px may be pushed this time
+        let mut a = rock((0.0, 0.0), (0.0, 0.0), 4.0);
+        let mut b = rock((15.0, 0.0), (0.0, 0.0), 1.0);
+        resolve_bounce(&mut a, &mut b, 1.0);
+        assert!((b.pos.0 - a.pos.0 - 16.0).abs() < 1e-4);
+        assert!((a.pos.0 + 0.2).abs() < 1e-4, "{:?}", a.pos);
+        assert!((b.pos.0 - 15.8).abs() < 1e-4, "{:?}", b.pos);
+    }
+}
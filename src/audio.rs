@@ -0,0 +1,52 @@
+use blasteroids::world::WorldEvent;
+use sdl2::mixer::{Channel, Chunk};
+use std::path::Path;
+
+/// Sound effects, all empty when muted so nothing ever touches the mixer.
+pub struct Sounds {
+    laser: Option<Chunk>,
+    explosion: Option<Chunk>,
+    hurt: Option<Chunk>,
+    extra_life: Option<Chunk>,
+    hyperspace: Option<Chunk>,
+    shield_hit: Option<Chunk>,
+    powerup: Option<Chunk>,
+}
+
+impl Sounds {
+    pub fn load(assets: &Path, mute: bool) -> Result<Self, String> {
+        let load = |name: &str| {
+            if mute {
+                Ok(None)
+            } else {
+                Chunk::from_file(assets.join(name)).map(Some)
+            }
+        };
+        Ok(Sounds {
+            laser: load("shoot.wav")?,
+            explosion: load("explosion.wav")?,
+            hurt: load("hurt.wav")?,
+            extra_life: load("extralife.wav")?,
+            hyperspace: load("hyperspace.wav")?,
+            shield_hit: load("shieldhit.wav")?,
+            powerup: load("powerup.wav")?,
+        })
+    }
+
+    /// Fire and forget, a sound that can't be played is just skipped.
+    pub fn play(&self, event: WorldEvent) {
+        let sfx = match event {
+            WorldEvent::Shot | WorldEvent::SaucerShot => &self.laser,
+            WorldEvent::AsteroidDestroyed | WorldEvent::SaucerDestroyed => &self.explosion,
+            WorldEvent::PlayerHit => &self.hurt,
+            WorldEvent::ExtraLife => &self.extra_life,
+            WorldEvent::Hyperspace => &self.hyperspace,
+            WorldEvent::ShieldHit => &self.shield_hit,
+            WorldEvent::PowerUp => &self.powerup,
+        };
+        // every channel busy in a big fight, missing one effect is fine
+        if let Some(sfx) = sfx {
+            let _ = Channel::all().play(sfx, 0);
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::path::PathBuf;
+
+/// Used when `--config` isn't given, it's fine for this one to be missing.
+pub const DEFAULT_CONFIG: &str = "config.toml";
+
+pub const USAGE: &str = "\
+Usage: Blasteroids [OPTIONS]
+
+Display:
+      --width <px>           Window width [default: 1280]
+      --height <px>          Window height [default: 840]
+      --fullscreen           Fill the whole screen
+      --mute                 Don't open audio at all
+
+Game:
+      --seed <n>             Replay a specific asteroid layout
+      --config <file>        Tuning file [default: config.toml]
+      --assets-dir <dir>     Where fonts, sounds and shapes live [default: assets]
+      --polygon-collisions   Check the actual outlines after the circle test
+      --bounce               Asteroids bounce off each other
+      --procedural           Generate asteroid outlines instead of using shapes.toml
+      --endless              Keep the field full instead of playing in waves
+
+Replays:
+      --record <file>        Save the run to a replay file on exit
+      --replay <file>        Play a replay file back with its own seed, tuning and mode
+
+  -h, --help                 Print this help
+";
+
+pub struct Options {
+    pub width: u32,
+    pub height: u32,
+    pub fullscreen: bool,
+    pub mute: bool,
+    pub seed: Option<u64>,
+    pub config: PathBuf,
+    pub assets_dir: PathBuf,
+    pub polygon_collisions: bool,
+    pub bounce: bool,
+    pub procedural: bool,
+    pub endless: bool,
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            width: 1280,
+            height: 840,
+            fullscreen: false,
+            mute: false,
+            seed: None,
+            config: DEFAULT_CONFIG.into(),
+            assets_dir: "assets".into(),
+            polygon_collisions: false,
+            bounce: false,
+            procedural: false,
+            endless: false,
+            record: None,
+            replay: None,
+            help: false,
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, String>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|err| format!("Invalid value '{}' for {}: {}", value, arg, err))
+}
+
+fn parse_size(arg: &str, value: &str) -> Result<u32, String> {
+    match parse_number(arg, value)? {
+        0 => Err(format!("{} must be at least 1", arg)),
+        size => Ok(size),
+    }
+}
+
+/// Parse everything after the program name, `--flag=value` works too.
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    // a replay brings its own seed, tuning, mode and window
+    let mut replay_overrides = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (arg, inline_value) = match arg.split_once('=') {
+            Some((arg, value)) if arg.starts_with("--") => (arg.to_string(), Some(value.into())),
+            _ => (arg, None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or(format!("{} needs a value, see --help", arg))
+        };
+        let flag = || match inline_value {
+            Some(_) => Err(format!("{} doesn't take a value, see --help", arg)),
+            None => Ok(true),
+        };
+        match arg.as_str() {
+            "--width" => options.width = parse_size(&arg, &value()?)?,
+            "--height" => options.height = parse_size(&arg, &value()?)?,
+            "--fullscreen" => options.fullscreen = flag()?,
+            "--mute" => options.mute = flag()?,
+            "--seed" => options.seed = Some(parse_number(&arg, &value()?)?),
+            "--config" => options.config = value()?.into(),
+            "--assets-dir" => options.assets_dir = value()?.into(),
+            "--polygon-collisions" => options.polygon_collisions = flag()?,
+            "--bounce" => options.bounce = flag()?,
+            "--procedural" => options.procedural = flag()?,
+            "--endless" => options.endless = flag()?,
+            "--record" => options.record = Some(value()?.into()),
+            "--replay" => options.replay = Some(value()?.into()),
+            "-h" | "--help" => options.help = flag()?,
+            _ => return Err(format!("Unknown argument '{}', see --help", arg)),
+        }
+        if matches!(
+            arg.as_str(),
+            "--width"
+                | "--height"
+                | "--fullscreen"
+                | "--seed"
+                | "--config"
+                | "--polygon-collisions"
+                | "--bounce"
+                | "--procedural"
+                | "--endless"
+                | "--record"
+        ) && !replay_overrides.contains(&arg)
+        {
+            replay_overrides.push(arg);
+        }
+    }
+    if options.replay.is_some() && !replay_overrides.is_empty() {
+        return Err(format!(
+            "{} can't be used with --replay, the replay brings its own",
+            replay_overrides.join(", ")
+        ));
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn values_can_follow_or_be_inline() {
+        let options = parse(&["--width", "800", "--height=600", "--seed=42", "--mute"]).unwrap();
+        assert_eq!((options.width, options.height), (800, 600));
+        assert_eq!(options.seed, Some(42));
+        assert!(options.mute);
+        assert!(!options.polygon_collisions);
+    }
+
+    #[test]
+    fn rejects_bad_values() {
+        assert!(parse(&["--width", "0"]).is_err());
+        assert!(parse(&["--seed", "-1"]).is_err());
+        assert!(parse(&["--config"]).is_err());
+        assert!(parse(&["--nope"]).is_err());
+    }
+
+    #[test]
+    fn flags_dont_take_values() {
+        let err = parse(&["--mute=false"]).err().unwrap();
+        assert!(err.contains("--mute doesn't take a value"), "{}", err);
+        assert!(parse(&["--polygon-collisions=yes"]).is_err());
+    }
+
+    #[test]
+    fn replay_refuses_what_it_overrides() {
+        assert!(parse(&["--replay", "run.blrp", "--mute", "--assets-dir=a"]).is_ok());
+        assert!(parse(&["--replay", "run.blrp", "--fullscreen"]).is_err());
+        let err = parse(&["--seed", "1", "--replay=run.blrp", "--endless", "--seed=2"])
+            .err()
+            .unwrap();
+        assert!(
+            err.starts_with("--seed, --endless can't be used with --replay"),
+            "{}",
+            err
+        );
+    }
+}
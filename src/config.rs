@@ -0,0 +1,928 @@
+use crate::asteroid::AsteroidSize;
+use crate::procgen::OutlineParams;
+use crate::saucer::SaucerSize;
+use crate::weapon::{PiercingShot, RapidBurst, SingleShot, TripleSpread, Weapon, WeaponKind};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{f32::consts::PI, ops::Range, path::Path, time::Duration};
+
+// ranges are written as [min, max] in the file
+fn de_range<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Range<f32>, D::Error> {
+    let [start, end] = <[f32; 2]>::deserialize(deserializer)?;
+    Ok(start..end)
+}
+
+fn ser_range<S: Serializer>(range: &Range<f32>, serializer: S) -> Result<S::Ok, S::Error> {
+    [range.start, range.end].serialize(serializer)
+}
+
+/// Tuning values for the whole game, see `config.toml` for what each one does.
+/// Every rate is per second.
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub ship: ShipConfig,
+    pub lives: LivesConfig,
+    pub hyperspace: HyperspaceConfig,
+    pub shield: ShieldConfig,
+    pub projectile: ProjectileConfig,
+    pub weapons: WeaponConfig,
+    pub asteroids: AsteroidConfig,
+    pub waves: WaveConfig,
+    pub saucers: SaucerConfig,
+    pub powerups: PowerUpConfig,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShipConfig {
+    pub turn_speed: f32, // in radian
+    pub acceleration: f32,
+    pub drag: f32, // fraction of velocity kept, smaller number -> stronger breaking
+    /// Collision circle, with polygon collisions the ship outline is used instead.
+    pub radius: f32,
+    pub iframe_duration: f32, // seconds, also after respawning
+    pub explode_on_hit: bool, // false: flash and keep flying instead of respawning
+    pub respawn_delay: f32,   // seconds before the ship may come back
+    pub safe_radius: f32,     // no asteroid may be this close to the centre to respawn
+}
+
+/// Extra lives are awarded at `extra_life_first` points and then every
+/// `extra_life_every` points after that, 0 turns either off.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LivesConfig {
+    pub starting: i32,
+    pub max: i32, // extra lives past this are lost
+    pub extra_life_first: i32,
+    pub extra_life_every: i32,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HyperspaceConfig {
+    pub cooldown: f32,             // seconds from one jump to the next
+    pub duration: f32,             // seconds the ship is gone, half fading out and half fading in
+    pub margin: f32,               // never land closer than this to the edge
+    pub self_destruct_chance: f32, // 0 to 1, rolled on re-entry
+}
+
+/// Energy goes from 0 to 1, the shield is up while it's held and there is energy left.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShieldConfig {
+    pub radius: f32,
+    pub drain: f32,    // energy per second while up
+    pub regen: f32,    // energy per second while released
+    pub hit_cost: f32, // energy per asteroid bounced off
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectileConfig {
+    pub speed: f32,
+}
+
+/// Tuning for each weapon, see `weapon.rs` for what they do.
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WeaponConfig {
+    pub single: SingleShot,
+    pub spread: TripleSpread,
+    pub rapid: RapidBurst,
+    pub piercing: PiercingShot,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AsteroidConfig {
+    pub starting_count: usize, // endless mode keeps the field filled up to this
+    pub margin: f32,           // how far off screen before wrapping
+    pub spin: f32,
+    pub large: SizeRules,
+    pub medium: SizeRules,
+    pub small: SizeRules,
+    pub procedural: OutlineParams, // outlines in procedural mode
+}
+
+/// How waves grow, wave `n` starts with
+/// `first_count + (n - 1) * count_step` large asteroids (up to `max_count`)
+/// moving `1 + (n - 1) * speed_step` times as fast (up to `max_speed_scale`).
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WaveConfig {
+    pub first_count: usize,
+    pub count_step: usize,
+    pub max_count: usize,
+    pub speed_step: f32,
+    pub max_speed_scale: f32,
+    pub delay: f32, // seconds between clearing a wave and the next one showing up
+}
+
+/// One saucer at a time crosses the screen. Small saucers show up past
+/// `small_from` points and get more common until every saucer is small at
+/// `small_only` points. Their aim improves from `aim_error_easy` at 0 points
+/// to `aim_error_hard` at `hardest_at` points.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SaucerConfig {
+    pub enabled: bool,
+    pub interval: f32,      // seconds between one saucer leaving and the next one
+    pub turn_interval: f32, // seconds between changes of direction
+    pub fire_interval: f32, // seconds between shots
+    pub projectile_speed: f32,
+    pub lead_targeting: bool, // small saucers aim where the ship is going, not where it is
+    pub aim_error_easy: f32,  // radians either way
+    pub aim_error_hard: f32,
+    pub hardest_at: i32,
+    pub small_from: i32,
+    pub small_only: i32,
+    pub large: SaucerRules,
+    pub small: SaucerRules,
+}
+
+/// Asteroids the player shoots down may leave a power-up behind, the kind is
+/// picked by weight. Rapid fire and spread hand over that weapon for
+/// `duration` seconds, the score multiplier lasts as long. Picking up another
+/// one starts the clock over.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PowerUpConfig {
+    pub drop_chance: f32, // 0 to 1, per asteroid
+    pub lifetime: f32,    // seconds before an uncollected power-up vanishes
+    pub speed: f32,
+    pub radius: f32,
+    pub duration: f32,
+    pub multiplier: i32,
+    pub weights: PowerUpWeights,
+    // old names for weapons.rapid.interval and weapons.spread.spread, moved
+    // over on parse so config files from before weapons keep working
+    #[serde(skip_serializing)]
+    rapid_fire_interval: Option<f32>,
+    #[serde(skip_serializing)]
+    spread_angle: Option<f32>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PowerUpWeights {
+    pub rapid_fire: u32,
+    pub spread: u32,
+    pub shield_recharge: u32,
+    pub extra_life: u32,
+    pub score_multiplier: u32,
+}
+
+/// How a saucer of one size looks, moves and scores.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaucerRules {
+    pub scale: f32,
+    pub speed: f32,
+    pub score: i32,
+}
+
+/// How an asteroid of one size looks, moves, scores and breaks apart.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SizeRules {
+    #[serde(deserialize_with = "de_range", serialize_with = "ser_range")]
+    pub scale_range: Range<f32>,
+    #[serde(deserialize_with = "de_range", serialize_with = "ser_range")]
+    pub speed_range: Range<f32>,
+    pub score: i32,
+    pub mass: f32, // at scale 1.0
+    /// Pieces of the next size down left behind when destroyed.
+    pub children: usize,
+}
+
+impl Default for ShipConfig {
+    fn default() -> Self {
+        ShipConfig {
+            turn_speed: 4.2,
+            acceleration: 720.0,
+            drag: 0.3,
+            radius: 10.0,
+            iframe_duration: 0.8,
+            explode_on_hit: true,
+            respawn_delay: 2.0,
+            safe_radius: 120.0,
+        }
+    }
+}
+
+impl Default for LivesConfig {
+    fn default() -> Self {
+        LivesConfig {
+            starting: 3,
+            max: 5,
+            extra_life_first: 10_000,
+            extra_life_every: 10_000,
+        }
+    }
+}
+
+impl Default for HyperspaceConfig {
+    fn default() -> Self {
+        HyperspaceConfig {
+            cooldown: 3.0,
+            duration: 0.6,
+            margin: 40.0,
+            self_destruct_chance: 0.05,
+        }
+    }
+}
+
+impl Default for ShieldConfig {
+    fn default() -> Self {
+        ShieldConfig {
+            radius: 30.0,
+            drain: 0.4,
+            regen: 0.1,
+            hit_cost: 0.1,
+        }
+    }
+}
+
+impl Default for ProjectileConfig {
+    fn default() -> Self {
+        ProjectileConfig { speed: 540.0 }
+    }
+}
+
+impl Default for WaveConfig {
+    fn default() -> Self {
+        WaveConfig {
+            first_count: 4,
+            count_step: 1,
+            max_count: 11,
+            speed_step: 0.1,
+            max_speed_scale: 2.0,
+            delay: 2.0,
+        }
+    }
+}
+
+impl Default for AsteroidConfig {
+    fn default() -> Self {
+        AsteroidConfig {
+            starting_count: 15,
+            margin: 40.0,
+            spin: 0.6,
+            large: SizeRules {
+                scale_range: 1.1..1.5,
+                speed_range: 60.0..180.0,
+                score: 20,
+                mass: 4.0,
+                children: 2,
+            },
+            medium: SizeRules {
+                scale_range: 0.7..1.0,
+                speed_range: 60.0..180.0,
+                score: 50,
+                mass: 1.0,
+                children: 2,
+            },
+            small: SizeRules {
+                scale_range: 0.35..0.5,
+                speed_range: 90.0..240.0,
+                score: 100,
+                mass: 0.25,
+                children: 0,
+            },
+            procedural: OutlineParams::default(),
+        }
+    }
+}
+
+impl Default for SaucerConfig {
+    fn default() -> Self {
+        SaucerConfig {
+            enabled: true,
+            interval: 15.0,
+            turn_interval: 1.5,
+            fire_interval: 1.0,
+            projectile_speed: 360.0,
+            lead_targeting: true,
+            aim_error_easy: 0.25,
+            aim_error_hard: 0.03,
+            hardest_at: 40_000,
+            small_from: 5_000,
+            small_only: 40_000,
+            large: SaucerRules {
+                scale: 1.0,
+                speed: 120.0,
+                score: 200,
+            },
+            small: SaucerRules {
+                scale: 0.6,
+                speed: 180.0,
+                score: 1000,
+            },
+        }
+    }
+}
+
+impl Default for PowerUpConfig {
+    fn default() -> Self {
+        PowerUpConfig {
+            drop_chance: 0.08,
+            lifetime: 10.0,
+            speed: 30.0,
+            radius: 16.0,
+            duration: 10.0,
+            multiplier: 2,
+            weights: PowerUpWeights::default(),
+            rapid_fire_interval: None,
+            spread_angle: None,
+        }
+    }
+}
+
+impl Default for PowerUpWeights {
+    fn default() -> Self {
+        PowerUpWeights {
+            rapid_fire: 3,
+            spread: 3,
+            shield_recharge: 2,
+            extra_life: 1,
+            score_multiplier: 2,
+        }
+    }
+}
+
+impl ShipConfig {
+    pub fn iframe_duration(&self) -> Duration {
+        Duration::from_secs_f32(self.iframe_duration)
+    }
+
+    pub fn respawn_delay(&self) -> Duration {
+        Duration::from_secs_f32(self.respawn_delay)
+    }
+}
+
+impl LivesConfig {
+    /// The first score that earns a life, `None` when extra lives are off.
+    pub fn first_threshold(&self) -> Option<i32> {
+        (self.extra_life_first > 0).then_some(self.extra_life_first)
+    }
+
+    /// The threshold after `previous`, `None` when there are no more.
+    pub fn next_threshold(&self, previous: i32) -> Option<i32> {
+        (self.extra_life_every > 0).then(|| previous.saturating_add(self.extra_life_every))
+    }
+}
+
+impl HyperspaceConfig {
+    pub fn cooldown(&self) -> Duration {
+        Duration::from_secs_f32(self.cooldown)
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f32(self.duration)
+    }
+}
+
+impl WaveConfig {
+    /// Large asteroids spawned at the start of wave `wave` (1 based).
+    pub fn count(&self, wave: u32) -> usize {
+        let extra = self
+            .count_step
+            .saturating_mul(wave.saturating_sub(1) as usize);
+        self.first_count.saturating_add(extra).min(self.max_count)
+    }
+
+    /// Multiplier on every asteroid speed during wave `wave` (1 based).
+    pub fn speed_scale(&self, wave: u32) -> f32 {
+        let extra = self.speed_step * wave.saturating_sub(1) as f32;
+        (1.0 + extra).min(self.max_speed_scale)
+    }
+
+    pub fn delay(&self) -> Duration {
+        Duration::from_secs_f32(self.delay)
+    }
+}
+
+impl SaucerConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs_f32(self.interval)
+    }
+
+    pub fn turn_interval(&self) -> Duration {
+        Duration::from_secs_f32(self.turn_interval)
+    }
+
+    pub fn fire_interval(&self) -> Duration {
+        Duration::from_secs_f32(self.fire_interval)
+    }
+
+    /// How far a small saucer's shot may miss at `score`, in radians either way.
+    pub fn aim_error(&self, score: i32) -> f32 {
+        let progress = (score as f32 / self.hardest_at as f32).clamp(0.0, 1.0);
+        self.aim_error_easy + (self.aim_error_hard - self.aim_error_easy) * progress
+    }
+
+    /// Chance the next saucer is a small one, from 0 at `small_from` to 1 at `small_only`.
+    pub fn small_chance(&self, score: i32) -> f32 {
+        let progress =
+            (score - self.small_from) as f32 / (self.small_only - self.small_from) as f32;
+        progress.clamp(0.0, 1.0)
+    }
+
+    pub fn size(&self, size: SaucerSize) -> &SaucerRules {
+        match size {
+            SaucerSize::Large => &self.large,
+            SaucerSize::Small => &self.small,
+        }
+    }
+}
+
+impl PowerUpConfig {
+    pub fn lifetime(&self) -> Duration {
+        Duration::from_secs_f32(self.lifetime)
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f32(self.duration)
+    }
+}
+
+impl PowerUpWeights {
+    fn total(&self) -> u64 {
+        [
+            self.rapid_fire,
+            self.spread,
+            self.shield_recharge,
+            self.extra_life,
+            self.score_multiplier,
+        ]
+        .iter()
+        .map(|&weight| u64::from(weight))
+        .sum()
+    }
+}
+
+impl WeaponConfig {
+    pub fn get(&self, kind: WeaponKind) -> &dyn Weapon {
+        match kind {
+            WeaponKind::Single => &self.single,
+            WeaponKind::Spread => &self.spread,
+            WeaponKind::Rapid => &self.rapid,
+            WeaponKind::Piercing => &self.piercing,
+        }
+    }
+}
+
+impl AsteroidConfig {
+    pub fn size(&self, size: AsteroidSize) -> &SizeRules {
+        match size {
+            AsteroidSize::Large => &self.large,
+            AsteroidSize::Medium => &self.medium,
+            AsteroidSize::Small => &self.small,
+        }
+    }
+}
+
+fn check(ok: bool, key: &str, requirement: &str) -> Result<(), String> {
+    if ok {
+        Ok(())
+    } else {
+        Err(format!("{} must be {}", key, requirement))
+    }
+}
+
+fn check_range(range: &Range<f32>, key: &str) -> Result<(), String> {
+    check(
+        range.start.is_finite() && range.end.is_finite() && 0.0 < range.start,
+        key,
+        "positive numbers",
+    )?;
+    check(range.start < range.end, key, "[min, max] with min < max")
+}
+
+impl SizeRules {
+    fn validate(&self, name: &str) -> Result<(), String> {
+        let key = |field: &str| format!("asteroids.{}.{}", name, field);
+        check_range(&self.scale_range, &key("scale_range"))?;
+        check_range(&self.speed_range, &key("speed_range"))?;
+        check(self.score >= 0, &key("score"), "0 or more")?;
+        check(
+            self.mass.is_finite() && self.mass > 0.0,
+            &key("mass"),
+            "positive",
+        )?;
+        check(self.children <= 16, &key("children"), "at most 16")
+    }
+}
+
+impl SaucerRules {
+    fn validate(&self, name: &str) -> Result<(), String> {
+        let key = |field: &str| format!("saucers.{}.{}", name, field);
+        check(
+            self.scale.is_finite() && self.scale > 0.0,
+            &key("scale"),
+            "positive",
+        )?;
+        check(
+            self.speed.is_finite() && self.speed > 0.0,
+            &key("speed"),
+            "positive",
+        )?;
+        check(self.score >= 0, &key("score"), "0 or more")
+    }
+}
+
+impl GameConfig {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut config: GameConfig = toml::from_str(text).map_err(|err| err.to_string())?;
+        if let Some(interval) = config.powerups.rapid_fire_interval.take() {
+            config.weapons.rapid.interval = interval;
+        }
+        if let Some(angle) = config.powerups.spread_angle.take() {
+            config.weapons.spread.spread = angle;
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+        Self::parse(&text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("config is always valid toml")
+    }
+
+    // types are checked by serde, this catches values that make no sense
+    fn validate(&self) -> Result<(), String> {
+        let ship = &self.ship;
+        check(
+            ship.turn_speed.is_finite() && ship.turn_speed >= 0.0,
+            "ship.turn_speed",
+            "0 or more",
+        )?;
+        check(
+            ship.acceleration.is_finite() && ship.acceleration >= 0.0,
+            "ship.acceleration",
+            "0 or more",
+        )?;
+        check(
+            0.0 < ship.drag && ship.drag <= 1.0,
+            "ship.drag",
+            "between 0 and 1",
+        )?;
+        check(
+            ship.radius.is_finite() && ship.radius > 0.0,
+            "ship.radius",
+            "positive",
+        )?;
+        check(
+            (0.0..60.0).contains(&ship.iframe_duration),
+            "ship.iframe_duration",
+            "between 0 and 60 seconds",
+        )?;
+        check(
+            (0.0..60.0).contains(&ship.respawn_delay),
+            "ship.respawn_delay",
+            "between 0 and 60 seconds",
+        )?;
+        check(
+            ship.safe_radius.is_finite() && ship.safe_radius >= 0.0,
+            "ship.safe_radius",
+            "0 or more",
+        )?;
+
+        let lives = &self.lives;
+        check(
+            0 < lives.starting && lives.starting <= lives.max,
+            "lives.starting",
+            "between 1 and lives.max",
+        )?;
+        check(lives.max <= 99, "lives.max", "at most 99")?;
+        check(
+            lives.extra_life_first >= 0,
+            "lives.extra_life_first",
+            "0 or more",
+        )?;
+        check(
+            lives.extra_life_every >= 0,
+            "lives.extra_life_every",
+            "0 or more",
+        )?;
+
+        let hyperspace = &self.hyperspace;
+        check(
+            (0.0..600.0).contains(&hyperspace.cooldown),
+            "hyperspace.cooldown",
+            "between 0 and 600 seconds",
+        )?;
+        check(
+            (0.0..60.0).contains(&hyperspace.duration),
+            "hyperspace.duration",
+            "between 0 and 60 seconds",
+        )?;
+        check(
+            hyperspace.margin.is_finite() && hyperspace.margin >= 0.0,
+            "hyperspace.margin",
+            "0 or more",
+        )?;
+        check(
+            (0.0..=1.0).contains(&hyperspace.self_destruct_chance),
+            "hyperspace.self_destruct_chance",
+            "between 0 and 1",
+        )?;
+
+        let shield = &self.shield;
+        check(
+            shield.radius.is_finite() && shield.radius > 0.0,
+            "shield.radius",
+            "positive",
+        )?;
+        check(
+            shield.drain.is_finite() && shield.drain >= 0.0,
+            "shield.drain",
+            "0 or more",
+        )?;
+        check(
+            shield.regen.is_finite() && shield.regen >= 0.0,
+            "shield.regen",
+            "0 or more",
+        )?;
+        check(
+            shield.hit_cost.is_finite() && shield.hit_cost >= 0.0,
+            "shield.hit_cost",
+            "0 or more",
+        )?;
+
+        check(
+            self.projectile.speed.is_finite() && self.projectile.speed > 0.0,
+            "projectile.speed",
+            "positive",
+        )?;
+
+        let weapons = &self.weapons;
+        for (key, cooldown) in [
+            ("weapons.single.cooldown", weapons.single.cooldown),
+            ("weapons.spread.cooldown", weapons.spread.cooldown),
+            ("weapons.piercing.cooldown", weapons.piercing.cooldown),
+        ] {
+            check(
+                (0.0..60.0).contains(&cooldown),
+                key,
+                "between 0 and 60 seconds",
+            )?;
+        }
+        check(
+            weapons.rapid.interval > 0.0 && weapons.rapid.interval < 60.0,
+            "weapons.rapid.interval",
+            "between 0 and 60 seconds",
+        )?;
+        check(
+            (0.0..=PI).contains(&weapons.spread.spread),
+            "weapons.spread.spread",
+            "between 0 and pi",
+        )?;
+        check(
+            weapons.piercing.speed.is_finite() && weapons.piercing.speed > 0.0,
+            "weapons.piercing.speed",
+            "positive",
+        )?;
+        check(
+            (1..=100).contains(&weapons.piercing.damage),
+            "weapons.piercing.damage",
+            "between 1 and 100",
+        )?;
+
+        let asteroids = &self.asteroids;
+        check(
+            asteroids.starting_count <= 10_000,
+            "asteroids.starting_count",
+            "at most 10000",
+        )?;
+        check(
+            asteroids.margin.is_finite() && asteroids.margin >= 0.0,
+            "asteroids.margin",
+            "0 or more",
+        )?;
+        check(asteroids.spin.is_finite(), "asteroids.spin", "a number")?;
+        asteroids.large.validate("large")?;
+        asteroids.medium.validate("medium")?;
+        asteroids.small.validate("small")?;
+        let procedural = &asteroids.procedural;
+        check(
+            3 <= *procedural.vertices.start()
+                && procedural.vertices.start() <= procedural.vertices.end()
+                && *procedural.vertices.end() <= 256,
+            "asteroids.procedural.vertices",
+            "[min, max] with 3 <= min <= max <= 256",
+        )?;
+        check(
+            procedural.radius.is_finite() && procedural.radius > 0.0,
+            "asteroids.procedural.radius",
+            "positive",
+        )?;
+        check(
+            (0.0..1.0).contains(&procedural.radius_jitter),
+            "asteroids.procedural.radius_jitter",
+            "0 or more and below 1",
+        )?;
+        check(
+            (0.0..0.5).contains(&procedural.angle_jitter),
+            "asteroids.procedural.angle_jitter",
+            "0 or more and below 0.5",
+        )?;
+        check(
+            (0.0..=1.0).contains(&procedural.concavity_chance),
+            "asteroids.procedural.concavity_chance",
+            "between 0 and 1",
+        )?;
+        check(
+            (0.0..1.0).contains(&procedural.concavity_depth),
+            "asteroids.procedural.concavity_depth",
+            "0 or more and below 1",
+        )?;
+
+        let waves = &self.waves;
+        check(
+            0 < waves.first_count && waves.first_count <= waves.max_count,
+            "waves.first_count",
+            "between 1 and waves.max_count",
+        )?;
+        check(
+            waves.max_count <= 10_000,
+            "waves.max_count",
+            "at most 10000",
+        )?;
+        check(
+            waves.speed_step.is_finite() && waves.speed_step >= 0.0,
+            "waves.speed_step",
+            "0 or more",
+        )?;
+        check(
+            waves.max_speed_scale.is_finite() && waves.max_speed_scale >= 1.0,
+            "waves.max_speed_scale",
+            "1 or more",
+        )?;
+        check(
+            (0.0..60.0).contains(&waves.delay),
+            "waves.delay",
+            "between 0 and 60 seconds",
+        )?;
+
+        let saucers = &self.saucers;
+        check(
+            (0.0..600.0).contains(&saucers.interval),
+            "saucers.interval",
+            "between 0 and 600 seconds",
+        )?;
+        check(
+            saucers.turn_interval > 0.0 && saucers.turn_interval < 600.0,
+            "saucers.turn_interval",
+            "between 0 and 600 seconds",
+        )?;
+        check(
+            saucers.fire_interval > 0.0 && saucers.fire_interval < 600.0,
+            "saucers.fire_interval",
+            "between 0 and 600 seconds",
+        )?;
+        check(
+            saucers.projectile_speed.is_finite() && saucers.projectile_speed > 0.0,
+            "saucers.projectile_speed",
+            "positive",
+        )?;
+        check(
+            (0.0..=PI).contains(&saucers.aim_error_easy),
+            "saucers.aim_error_easy",
+            "between 0 and pi",
+        )?;
+        check(
+            (0.0..=PI).contains(&saucers.aim_error_hard),
+            "saucers.aim_error_hard",
+            "between 0 and pi",
+        )?;
+        check(saucers.hardest_at > 0, "saucers.hardest_at", "positive")?;
+        check(saucers.small_from >= 0, "saucers.small_from", "0 or more")?;
+        check(
+            saucers.small_only > saucers.small_from,
+            "saucers.small_only",
+            "more than saucers.small_from",
+        )?;
+        saucers.large.validate("large")?;
+        saucers.small.validate("small")?;
+
+        let powerups = &self.powerups;
+        check(
+            (0.0..=1.0).contains(&powerups.drop_chance),
+            "powerups.drop_chance",
+            "between 0 and 1",
+        )?;
+        check(
+            powerups.lifetime > 0.0 && powerups.lifetime < 600.0,
+            "powerups.lifetime",
+            "between 0 and 600 seconds",
+        )?;
+        check(
+            powerups.speed.is_finite() && powerups.speed >= 0.0,
+            "powerups.speed",
+            "0 or more",
+        )?;
+        check(
+            powerups.radius.is_finite() && powerups.radius > 0.0,
+            "powerups.radius",
+            "positive",
+        )?;
+        check(
+            (0.0..600.0).contains(&powerups.duration),
+            "powerups.duration",
+            "between 0 and 600 seconds",
+        )?;
+        check(
+            (1..=100).contains(&powerups.multiplier),
+            "powerups.multiplier",
+            "between 1 and 100",
+        )?;
+        check(
+            (1..=u64::from(u32::MAX)).contains(&powerups.weights.total()),
+            "powerups.weights",
+            "at least one positive weight, adding up to at most 4294967295",
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_config_matches_the_defaults() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("config.toml");
+        let config = GameConfig::load(&path).unwrap();
+        assert_eq!(config.to_toml(), GameConfig::default().to_toml());
+    }
+
+    #[test]
+    fn old_powerup_keys_move_to_the_weapons() {
+        let text = "[powerups]\nrapid_fire_interval = 0.3\nspread_angle = 0.5\n";
+        let config = GameConfig::parse(text).unwrap();
+        assert_eq!(config.weapons.rapid.interval, 0.3);
+        assert_eq!(config.weapons.spread.spread, 0.5);
+        assert!(!config.to_toml().contains("spread_angle"));
+
+        let err = GameConfig::parse("[powerups]\nspread_angle = 9.0\n").err();
+        assert_eq!(
+            err.unwrap(),
+            "weapons.spread.spread must be between 0 and pi"
+        );
+    }
+
+    #[test]
+    fn rejects_infinite_values() {
+        for (table, key) in [
+            ("[ship]\nturn_speed = inf", "ship.turn_speed"),
+            ("[ship]\nacceleration = inf", "ship.acceleration"),
+            ("[ship]\nradius = inf", "ship.radius"),
+            ("[shield]\nradius = inf", "shield.radius"),
+            ("[shield]\nregen = inf", "shield.regen"),
+            ("[projectile]\nspeed = inf", "projectile.speed"),
+            ("[weapons.piercing]\nspeed = inf", "weapons.piercing.speed"),
+            ("[asteroids]\nmargin = inf", "asteroids.margin"),
+            (
+                "[asteroids.large]\nscale_range = [1.0, 2.0]\nspeed_range = [1.0, 2.0]\n\
+                 score = 1\nmass = inf\nchildren = 2",
+                "asteroids.large.mass",
+            ),
+            (
+                "[saucers]\nprojectile_speed = inf",
+                "saucers.projectile_speed",
+            ),
+            (
+                "[saucers.small]\nscale = 1.0\nspeed = inf\nscore = 1",
+                "saucers.small.speed",
+            ),
+            ("[powerups]\nradius = inf", "powerups.radius"),
+            ("[powerups]\nspeed = nan", "powerups.speed"),
+        ] {
+            let err = GameConfig::parse(table).err().unwrap();
+            assert!(err.starts_with(key), "{}: {}", table, err);
+        }
+    }
+
+    #[test]
+    fn rejects_procedural_outlines_that_make_no_sense() {
+        for table in [
+            "vertices = [2, 8]",
+            "vertices = [12, 8]",
+            "radius = 0.0",
+            "angle_jitter = 0.5",
+            "concavity_depth = 1.0",
+        ] {
+            let text = format!("[asteroids.procedural]\n{}\n", table);
+            let err = GameConfig::parse(&text).err().unwrap();
+            assert!(err.starts_with("asteroids.procedural."), "{}", err);
+        }
+        let text = "[asteroids.procedural]\nvertices = [5, 5]\n";
+        let config = GameConfig::parse(text).unwrap();
+        assert_eq!(config.asteroids.procedural.vertices, 5..=5);
+    }
+}
@@ -0,0 +1,227 @@
+use std::f32::consts::{PI, TAU};
+
+pub type Vec2 = (f32, f32);
+
+// take outline coords, apply offset, return position
+pub fn translate(points: &[Vec2], offset: Vec2) -> Vec<Vec2> {
+    points
+        .iter()
+        .map(|p| (p.0 + offset.0, p.1 + offset.1))
+        .collect()
+}
+
+pub fn scale_outline(points: &[Vec2], scale: f32) -> Vec<Vec2> {
+    points.iter().map(|p| (p.0 * scale, p.1 * scale)).collect()
+}
+
+pub fn rotate(points: &[Vec2], angle: f32) -> Vec<Vec2> {
+    let (sin_a, cos_a) = angle.sin_cos();
+    points
+        .iter()
+        .map(|p| (p.0 * cos_a - p.1 * sin_a, p.0 * sin_a + p.1 * cos_a))
+        .collect()
+}
+
+// max dist from origin
+pub fn outline_radius(points: &[Vec2]) -> f32 {
+    points
+        .iter()
+        .map(|p| (p.0 * p.0 + p.1 * p.1).sqrt())
+        .fold(0.0, f32::max)
+}
+
+// keep position relative to screen
+pub fn adjust_pos_for_resize(pos: &mut Vec2, old_viewport: (u32, u32), new_viewport: (u32, u32)) {
+    let old_center_x = old_viewport.0 as f32 / 2.0;
+    let old_center_y = old_viewport.1 as f32 / 2.0;
+    let offset_x = pos.0 - old_center_x;
+    let offset_y = pos.1 - old_center_y;
+
+    let new_center_x = new_viewport.0 as f32 / 2.0;
+    let new_center_y = new_viewport.1 as f32 / 2.0;
+    pos.0 = new_center_x + offset_x;
+    pos.1 = new_center_y + offset_y;
+}
+
+pub fn check_collision(a_pos: Vec2, a_radius: f32, b_pos: Vec2, b_radius: f32) -> bool {
+    let dx = a_pos.0 - b_pos.0;
+    let dy = a_pos.1 - b_pos.1;
+    let sum = a_radius + b_radius;
+    dx * dx + dy * dy <= sum * sum
+}
+
+pub fn wrap_position(pos: &mut Vec2, viewport: (u32, u32), margin: f32) {
+    let (w, h) = (viewport.0 as f32, viewport.1 as f32);
+    if pos.0 < -margin {
+        pos.0 = w + margin;
+    } else if pos.0 > w + margin {
+        pos.0 = -margin;
+    }
+
+    if pos.1 < -margin {
+        pos.1 = h + margin;
+    } else if pos.1 > h + margin {
+        pos.1 = -margin;
+    }
+}
+
+// unit vector the ship / projectile points at, 0 rad = up
+pub fn heading(angle: f32) -> Vec2 {
+    let (sin, cos) = angle.sin_cos();
+    (sin, -cos)
+}
+
+// keep angle < 360
+pub fn normalize_angle(angle: f32) -> f32 {
+    (angle + TAU) % TAU
+}
+
+// blend between last and current tick, snap when the move was a screen wrap
+pub fn interpolate_pos(prev: Vec2, cur: Vec2, alpha: f32, viewport: (u32, u32)) -> Vec2 {
+    let dx = cur.0 - prev.0;
+    let dy = cur.1 - prev.1;
+    if dx.abs() > viewport.0 as f32 / 2.0 || dy.abs() > viewport.1 as f32 / 2.0 {
+        return cur;
+    }
+    (prev.0 + dx * alpha, prev.1 + dy * alpha)
+}
+
+// blend angles the short way round
+pub fn interpolate_angle(prev: f32, cur: f32, alpha: f32) -> f32 {
+    let diff = (cur - prev + PI).rem_euclid(TAU) - PI;
+    prev + diff * alpha
+}
+
+fn cross(o: Vec2, a: Vec2, b: Vec2) -> f32 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+pub fn segments_intersect(a1: Vec2, a2: Vec2, b1: Vec2, b2: Vec2) -> bool {
+    let d1 = cross(b1, b2, a1);
+    let d2 = cross(b1, b2, a2);
+    let d3 = cross(a1, a2, b1);
+    let d4 = cross(a1, a2, b2);
+    if d1 == 0.0 && d2 == 0.0 {
+        // on the same line, only a hit where they overlap
+        let overlap = |a: f32, b: f32, c: f32, d: f32| a.min(b) <= c.max(d) && c.min(d) <= a.max(b);
+        return overlap(a1.0, a2.0, b1.0, b2.0) && overlap(a1.1, a2.1, b1.1, b2.1);
+    }
+    // touching counts as hit
+    (d1 * d2 <= 0.0) && (d3 * d4 <= 0.0)
+}
+
+// even-odd rule, outline is closed (last point == first point)
+pub fn point_in_polygon(point: Vec2, polygon: &[Vec2]) -> bool {
+    let mut inside = false;
+    for edge in polygon.windows(2) {
+        let (a, b) = (edge[0], edge[1]);
+        if (a.1 > point.1) != (b.1 > point.1) {
+            let x = a.0 + (point.1 - a.1) / (b.1 - a.1) * (b.0 - a.0);
+            if point.0 < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Two outlines touch when any edges cross or one lies completely inside the other.
+/// `a` may be an open polyline (e.g. a single projectile segment), `b` must be closed.
+pub fn outlines_intersect(a: &[Vec2], b: &[Vec2]) -> bool {
+    let crossing = a.windows(2).any(|ea| {
+        b.windows(2)
+            .any(|eb| segments_intersect(ea[0], ea[1], eb[0], eb[1]))
+    });
+    crossing
+        || a.first().is_some_and(|&p| point_in_polygon(p, b))
+        || (is_closed(a) && b.first().is_some_and(|&p| point_in_polygon(p, a)))
+}
+
+fn is_closed(outline: &[Vec2]) -> bool {
+    outline.len() > 2 && outline.first() == outline.last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32, size: f32) -> Vec<Vec2> {
+        vec![
+            (x, y),
+            (x + size, y),
+            (x + size, y + size),
+            (x, y + size),
+            (x, y),
+        ]
+    }
+
+    // a U opening downwards (+y), the gap is x 10..20 from y 10 to the bottom
+    fn u_shape() -> Vec<Vec2> {
+        vec![
+            (0.0, 0.0),
+            (30.0, 0.0),
+            (30.0, 30.0),
+            (20.0, 30.0),
+            (20.0, 10.0),
+            (10.0, 10.0),
+            (10.0, 30.0),
+            (0.0, 30.0),
+            (0.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn a_shot_into_a_concave_gap_misses() {
+        let u = u_shape();
+        assert!(!point_in_polygon((15.0, 20.0), &u));
+        assert!(!outlines_intersect(&[(15.0, 40.0), (15.0, 12.0)], &u));
+        assert!(outlines_intersect(&[(15.0, 40.0), (15.0, 5.0)], &u));
+    }
+
+    #[test]
+    fn touching_counts_as_a_hit() {
+        assert!(segments_intersect(
+            (5.0, -5.0),
+            (5.0, 0.0),
+            (0.0, 0.0),
+            (10.0, 0.0)
+        ));
+        assert!(outlines_intersect(
+            &square(0.0, 0.0, 10.0),
+            &square(10.0, 0.0, 10.0)
+        ));
+        assert!(!outlines_intersect(
+            &square(0.0, 0.0, 10.0),
+            &square(10.5, 0.0, 10.0)
+        ));
+        // edges on the same line only touch where they overlap
+        assert!(segments_intersect(
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 0.0),
+            (20.0, 0.0)
+        ));
+        assert!(!segments_intersect(
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (11.0, 0.0),
+            (20.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn an_outline_inside_the_other_intersects() {
+        let (big, small) = (square(0.0, 0.0, 100.0), square(40.0, 40.0, 10.0));
+        assert!(outlines_intersect(&small, &big));
+        assert!(outlines_intersect(&big, &small));
+    }
+
+    #[test]
+    fn an_open_segment_against_a_closed_outline() {
+        let outline = square(0.0, 0.0, 10.0);
+        // fully inside, no edge crossed
+        assert!(outlines_intersect(&[(4.0, 4.0), (6.0, 6.0)], &outline));
+        assert!(outlines_intersect(&[(-5.0, 5.0), (5.0, 5.0)], &outline));
+        assert!(!outlines_intersect(&[(-5.0, 5.0), (-1.0, 5.0)], &outline));
+    }
+}
@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+// bump when the file layout changes, older files are converted on load
+//   v1: score only
+//   v2: initials and date
+const VERSION: u32 = 2;
+pub const MAX_ENTRIES: usize = 10;
+pub const INITIALS_LEN: usize = 3;
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct HighScore {
+    #[serde(default = "unknown_initials")]
+    pub initials: String,
+    pub score: i32,
+    #[serde(default)]
+    pub date: String, // YYYY-MM-DD, empty for v1 entries
+}
+
+// v1 entries were saved before anyone could sign them
+fn unknown_initials() -> String {
+    "???".to_string()
+}
+
+/// Today's date as YYYY-MM-DD (UTC).
+pub fn today() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// days since 1970-01-01 to a calendar date, see
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097); // day of era
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365; // year of era
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // day of year, starting in march
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct HighScoreFile {
+    version: u32,
+    #[serde(default)]
+    scores: Vec<HighScore>,
+}
+
+// only the version, so a newer file gives a clear error instead of a field mismatch
+#[derive(Deserialize)]
+struct VersionOnly {
+    version: u32,
+}
+
+/// Best scores first, never more than `MAX_ENTRIES`.
+#[derive(Clone, Default, Debug)]
+pub struct HighScores {
+    entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// `$XDG_DATA_HOME/blasteroids/highscores.toml`, falling back to `~/.local/share`.
+    pub fn default_path() -> Option<PathBuf> {
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share"))
+            })?;
+        Some(data_home.join("blasteroids").join("highscores.toml"))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let VersionOnly { version } = toml::from_str(text).map_err(|err| err.to_string())?;
+        if version == 0 || version > VERSION {
+            return Err(format!(
+                "Unsupported high score version {} (expected {})",
+                version, VERSION
+            ));
+        }
+        let file: HighScoreFile = toml::from_str(text).map_err(|err| err.to_string())?;
+        let mut scores = HighScores::default();
+        for entry in file.scores {
+            scores.insert(entry);
+        }
+        Ok(scores)
+    }
+
+    /// A missing file is just an empty table.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(HighScores::default());
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+        Self::parse(&text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn to_toml(&self) -> String {
+        let file = HighScoreFile {
+            version: VERSION,
+            scores: self.entries.clone(),
+        };
+        toml::to_string(&file).expect("high scores are always valid toml")
+    }
+
+    /// Write next to the real file and rename over it, so a crash never leaves half a table.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("Failed to create {}: {}", dir.display(), err))?;
+        }
+        let tmp = path.with_extension("toml.tmp");
+        fs::write(&tmp, self.to_toml())
+            .map_err(|err| format!("Failed to write {}: {}", tmp.display(), err))?;
+        fs::rename(&tmp, path)
+            .map_err(|err| format!("Failed to replace {}: {}", path.display(), err))
+    }
+
+    pub fn entries(&self) -> &[HighScore] {
+        &self.entries
+    }
+
+    /// Would this score make it onto the table.
+    pub fn qualifies(&self, score: i32) -> bool {
+        score > 0
+            && (self.entries.len() < MAX_ENTRIES
+                || self.entries.last().is_some_and(|last| score > last.score))
+    }
+
+    /// Add an entry and return its 0 based rank, `None` if it didn't make the cut.
+    /// Ties go below the existing entries, first to get there keeps the spot.
+    pub fn insert(&mut self, entry: HighScore) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        let rank = self.entries.partition_point(|e| e.score >= entry.score);
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_ENTRIES);
+        Some(rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(initials: &str, score: i32) -> HighScore {
+        HighScore {
+            initials: initials.to_string(),
+            score,
+            date: "2024-01-01".to_string(),
+        }
+    }
+
+    fn scores(table: &HighScores) -> Vec<(&str, i32)> {
+        table
+            .entries()
+            .iter()
+            .map(|e| (e.initials.as_str(), e.score))
+            .collect()
+    }
+
+    #[test]
+    fn insert_keeps_the_best_first_and_ties_below() {
+        let mut table = HighScores::default();
+        assert_eq!(table.insert(entry("AAA", 100)), Some(0));
+        assert_eq!(table.insert(entry("BBB", 300)), Some(0));
+        assert_eq!(table.insert(entry("CCC", 100)), Some(2));
+        assert_eq!(table.insert(entry("DDD", 200)), Some(1));
+        assert_eq!(table.insert(entry("EEE", 0)), None);
+        assert_eq!(
+            scores(&table),
+            [("BBB", 300), ("DDD", 200), ("AAA", 100), ("CCC", 100)]
+        );
+    }
+
+    #[test]
+    fn a_full_table_needs_a_better_score() {
+        let mut table = HighScores::default();
+        for score in 1..=MAX_ENTRIES as i32 {
+            table.insert(entry("AAA", score * 10));
+        }
+        assert!(!table.qualifies(10));
+        assert_eq!(table.insert(entry("BBB", 10)), None);
+        assert_eq!(table.insert(entry("CCC", 15)), Some(MAX_ENTRIES - 1));
+        assert_eq!(table.entries().len(), MAX_ENTRIES);
+        assert_eq!(table.entries().last().unwrap().score, 15);
+    }
+
+    #[test]
+    fn parse_sorts_and_converts_v1() {
+        let text = "version = 1\n\
+                    [[scores]]\nscore = 50\n\
+                    [[scores]]\nscore = 70\n";
+        let table = HighScores::parse(text).unwrap();
+        assert_eq!(scores(&table), [("???", 70), ("???", 50)]);
+
+        let round_trip = HighScores::parse(&table.to_toml()).unwrap();
+        assert_eq!(round_trip.entries(), table.entries());
+    }
+
+    #[test]
+    fn parse_rejects_newer_versions() {
+        let err = HighScores::parse("version = 3\n").err().unwrap();
+        assert!(err.contains("Unsupported high score version 3"), "{}", err);
+    }
+}
@@ -0,0 +1,14 @@
+// Game simulation, kept free of SDL so it can be tested and run headless.
+pub mod aim;
+pub mod asteroid;
+pub mod config;
+pub mod geometry;
+pub mod highscores;
+pub mod powerup;
+pub mod procgen;
+pub mod replay;
+pub mod saucer;
+pub mod shapes;
+pub mod spatial;
+pub mod weapon;
+pub mod world;
@@ -0,0 +1,70 @@
+use crate::config::PowerUpConfig;
+use crate::geometry::{heading, Vec2};
+use rand::Rng;
+use std::{f32::consts::TAU, time::Duration};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PowerUpKind {
+    RapidFire,
+    Spread,
+    ShieldRecharge,
+    ExtraLife,
+    ScoreMultiplier,
+}
+
+impl PowerUpKind {
+    /// Lasts `duration` once collected, the others take effect right away.
+    pub fn is_timed(self) -> bool {
+        matches!(
+            self,
+            PowerUpKind::RapidFire | PowerUpKind::Spread | PowerUpKind::ScoreMultiplier
+        )
+    }
+}
+
+/// A power-up drifting where an asteroid used to be, waiting to be flown into.
+pub struct PowerUp {
+    pub pos: Vec2,
+    pub vel: Vec2, // px per second
+    pub prev_pos: Vec2,
+    pub kind: PowerUpKind,
+    pub dropped: Duration,
+}
+
+/// Pick a kind, each one as likely as its weight says.
+pub fn pick_powerup_kind(rng: &mut impl Rng, config: &PowerUpConfig) -> PowerUpKind {
+    let weights = &config.weights;
+    let choices = [
+        (PowerUpKind::RapidFire, weights.rapid_fire),
+        (PowerUpKind::Spread, weights.spread),
+        (PowerUpKind::ShieldRecharge, weights.shield_recharge),
+        (PowerUpKind::ExtraLife, weights.extra_life),
+        (PowerUpKind::ScoreMultiplier, weights.score_multiplier),
+    ];
+    let total: u32 = choices.iter().map(|&(_, weight)| weight).sum();
+    let mut roll = rng.gen_range(0..total);
+    for (kind, weight) in choices {
+        if roll < weight {
+            return kind;
+        }
+        roll -= weight;
+    }
+    unreachable!("roll is below the total weight")
+}
+
+pub fn spawn_powerup(
+    kind: PowerUpKind,
+    pos: Vec2,
+    time: Duration,
+    config: &PowerUpConfig,
+    rng: &mut impl Rng,
+) -> PowerUp {
+    let dir = heading(rng.gen_range(0.0..TAU));
+    PowerUp {
+        pos,
+        vel: (config.speed * dir.0, config.speed * dir.1),
+        prev_pos: pos,
+        kind,
+        dropped: time,
+    }
+}
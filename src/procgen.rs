@@ -0,0 +1,74 @@
+use crate::geometry::Vec2;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{f32::consts::TAU, ops::RangeInclusive};
+
+// written as [min, max] in the config file like the other ranges
+fn de_vertices<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<RangeInclusive<usize>, D::Error> {
+    let [min, max] = <[usize; 2]>::deserialize(deserializer)?;
+    Ok(min..=max)
+}
+
+fn ser_vertices<S: Serializer>(
+    range: &RangeInclusive<usize>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    [*range.start(), *range.end()].serialize(serializer)
+}
+
+/// Knobs for `generate_outline`, sizes are in outline units (scale 1.0).
+/// Tuned in the `[asteroids.procedural]` config table.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutlineParams {
+    #[serde(deserialize_with = "de_vertices", serialize_with = "ser_vertices")]
+    pub vertices: RangeInclusive<usize>,
+    pub radius: f32,
+    /// Random +- fraction applied to every vertex distance.
+    pub radius_jitter: f32,
+    /// Random +- fraction of the even spacing applied to every vertex angle, keep below 0.5
+    /// so vertices never swap order and the outline can't cross itself.
+    pub angle_jitter: f32,
+    /// Chance a vertex is pulled in to form a dent.
+    pub concavity_chance: f64,
+    /// How far (fraction of its distance) a dent vertex is pulled in.
+    pub concavity_depth: f32,
+}
+
+impl Default for OutlineParams {
+    // roughly matches the hand drawn outlines in assets/shapes.toml
+    fn default() -> Self {
+        OutlineParams {
+            vertices: 10..=16,
+            radius: 28.0,
+            radius_jitter: 0.15,
+            angle_jitter: 0.3,
+            concavity_chance: 0.25,
+            concavity_depth: 0.45,
+        }
+    }
+}
+
+/// Closed jagged polygon around the origin, the same seed always gives the same outline.
+pub fn generate_outline(params: &OutlineParams, seed: u64) -> Vec<Vec2> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let count = rng.gen_range(params.vertices.clone()).max(3);
+    let step = TAU / count as f32;
+
+    let mut points: Vec<Vec2> = (0..count)
+        .map(|i| {
+            let angle = (i as f32 + rng.gen_range(-1.0..=1.0) * params.angle_jitter) * step;
+            let mut radius =
+                params.radius * (1.0 + rng.gen_range(-1.0..=1.0) * params.radius_jitter);
+            if rng.gen_bool(params.concavity_chance) {
+                radius *= 1.0 - params.concavity_depth;
+            }
+            let (sin, cos) = angle.sin_cos();
+            (radius * sin, -radius * cos)
+        })
+        .collect();
+    points.push(points[0]);
+    points
+}
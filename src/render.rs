@@ -0,0 +1,375 @@
+use blasteroids::{
+    geometry::{interpolate_angle, interpolate_pos, rotate, scale_outline, translate, Vec2},
+    powerup::PowerUpKind,
+    world::World,
+};
+use sdl2::{
+    pixels::Color,
+    rect::{Point, Rect},
+    render::{BlendMode, Canvas, TextureCreator},
+    ttf::{Font, Sdl2TtfContext},
+    video::{Window, WindowContext},
+};
+use std::{path::Path, time::Duration};
+
+pub const WHITE: Color = Color::RGB(255, 255, 255);
+pub const GREY: Color = Color::RGB(150, 150, 150);
+
+const EXTRA_LIFE_FLASH: Duration = Duration::from_millis(1500);
+const EXPLOSION_TIME: Duration = Duration::from_millis(1200);
+const DEBRIS_SPEED: f32 = 60.0; // px per second
+const DEBRIS_SPIN: f32 = 3.0; // radians per second
+const CIRCLE_SEGMENTS: usize = 24;
+const ENERGY_BAR: (u32, u32) = (100, 8);
+const POWERUP_WARNING: Duration = Duration::from_secs(2); // blink before vanishing
+
+#[derive(Clone, Copy)]
+pub enum FontSize {
+    Small,
+    Medium,
+    Large,
+}
+
+#[derive(Clone, Copy)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+// rotate outline, move it to pos and round to screen pixels
+pub fn to_screen_points(points: &[Vec2], angle: f32, pos: Vec2) -> Vec<Point> {
+    translate(&rotate(points, angle), pos)
+        .iter()
+        .map(|p| Point::new(p.0.round() as i32, p.1.round() as i32))
+        .collect()
+}
+
+/// The window plus everything needed to draw text into it.
+pub struct Renderer<'ttf> {
+    pub canvas: Canvas<Window>,
+    texture_creator: TextureCreator<WindowContext>,
+    small: Font<'ttf, 'static>,
+    medium: Font<'ttf, 'static>,
+    large: Font<'ttf, 'static>,
+}
+
+impl<'ttf> Renderer<'ttf> {
+    pub fn new(
+        canvas: Canvas<Window>,
+        ttf: &'ttf Sdl2TtfContext,
+        font_path: &Path,
+    ) -> Result<Self, String> {
+        let texture_creator = canvas.texture_creator();
+        Ok(Renderer {
+            canvas,
+            texture_creator,
+            small: ttf.load_font(font_path, 28)?,
+            medium: ttf.load_font(font_path, 50)?,
+            large: ttf.load_font(font_path, 110)?,
+        })
+    }
+
+    // same units as resize events
+    pub fn size(&self) -> (u32, u32) {
+        self.canvas.window().size()
+    }
+
+    pub fn clear(&mut self) {
+        self.canvas.set_draw_color(Color::RGB(0, 0, 0));
+        self.canvas.clear();
+        self.canvas.set_draw_color(WHITE);
+    }
+
+    /// Darken whatever is already drawn so text on top stays readable.
+    pub fn dim(&mut self) -> Result<(), String> {
+        self.canvas.set_blend_mode(BlendMode::Blend);
+        self.canvas.set_draw_color(Color::RGBA(0, 0, 0, 170));
+        self.canvas.fill_rect(None)?;
+        self.canvas.set_blend_mode(BlendMode::None);
+        self.canvas.set_draw_color(WHITE);
+        Ok(())
+    }
+
+    pub fn outline(&mut self, points: &[Vec2], angle: f32, pos: Vec2) -> Result<(), String> {
+        self.canvas
+            .draw_lines(to_screen_points(points, angle, pos).as_slice())
+    }
+
+    /// Draw one line of text, `y` is the top edge and `x` depends on `align`.
+    /// Returns the height so callers can stack lines.
+    pub fn text(
+        &mut self,
+        text: &str,
+        size: FontSize,
+        (x, y): (i32, i32),
+        align: Align,
+        color: Color,
+    ) -> Result<u32, String> {
+        let font = match size {
+            FontSize::Small => &self.small,
+            FontSize::Medium => &self.medium,
+            FontSize::Large => &self.large,
+        };
+        if text.is_empty() {
+            return Ok(font.height() as u32);
+        }
+        let surface = font
+            .render(text)
+            .blended(color)
+            .map_err(|err| err.to_string())?;
+        let (w, h) = surface.size();
+        let texture = self
+            .texture_creator
+            .create_texture_from_surface(&surface)
+            .map_err(|err| err.to_string())?;
+        let x = match align {
+            Align::Left => x,
+            Align::Center => x - w as i32 / 2,
+            Align::Right => x - w as i32,
+        };
+        self.canvas
+            .copy(&texture, None, Some(Rect::new(x, y, w, h)))?;
+        Ok(h)
+    }
+
+    // a circle with the kind written inside
+    fn powerup(&mut self, world: &World, kind: PowerUpKind, pos: Vec2) -> Result<(), String> {
+        let config = &world.config().powerups;
+        self.outline(&circle(config.radius), 0.0, pos)?;
+        let label = match kind {
+            PowerUpKind::RapidFire => "R".to_string(),
+            PowerUpKind::Spread => "S".to_string(),
+            PowerUpKind::ShieldRecharge => "E".to_string(),
+            PowerUpKind::ExtraLife => "L".to_string(),
+            PowerUpKind::ScoreMultiplier => format!("x{}", config.multiplier),
+        };
+        let top = pos.1 as i32 - self.small.height() / 2;
+        self.text(
+            &label,
+            FontSize::Small,
+            (pos.0 as i32, top),
+            Align::Center,
+            WHITE,
+        )?;
+        Ok(())
+    }
+
+    /// Asteroids, projectiles and the ship, `alpha` is how far into the next tick we are.
+    pub fn world(&mut self, world: &World, alpha: f32) -> Result<(), String> {
+        let viewport = world.viewport;
+        let shapes = world.shapes();
+
+        // draw asteroids
+        for asteroid in &world.asteroids {
+            let pos = interpolate_pos(asteroid.prev_pos, asteroid.pos, alpha, viewport);
+            let angle = interpolate_angle(asteroid.prev_angle, asteroid.angle, alpha);
+            self.outline(&asteroid.shape, angle, pos)?;
+        }
+
+        if let Some(saucer) = &world.saucer {
+            let pos = interpolate_pos(saucer.prev_pos, saucer.pos, alpha, viewport);
+            self.outline(&saucer.shape, 0.0, pos)?;
+        }
+
+        // draw projectiles
+        for p in world.projectiles.iter().chain(&world.saucer_projectiles) {
+            let pos = interpolate_pos(p.prev_pos, p.pos, alpha, viewport);
+            self.outline(&shapes.projectile, p.angle, pos)?;
+        }
+
+        // draw power-ups, blinking when they're about to vanish
+        let lifetime = world.config().powerups.lifetime();
+        for powerup in &world.powerups {
+            let left = lifetime.saturating_sub(world.powerup_age(powerup));
+            if left < POWERUP_WARNING && !(left.as_millis() / 150).is_multiple_of(2) {
+                continue;
+            }
+            let pos = interpolate_pos(powerup.prev_pos, powerup.pos, alpha, viewport);
+            self.powerup(world, powerup.kind, pos)?;
+        }
+
+        let player = &world.player;
+        if let Some(since) = world.since_death() {
+            return self.explosion(&shapes.ship, player.angle, player.pos, since);
+        }
+        // draw player, gone once the last life is lost
+        if world.is_game_over() {
+            return Ok(());
+        }
+        // fold away at the start, unfold at the destination
+        if let Some(jump) = world.jump() {
+            let (pos, scale) = if jump.progress < 0.5 {
+                (jump.from, 1.0 - jump.progress * 2.0)
+            } else {
+                (jump.to, jump.progress * 2.0 - 1.0)
+            };
+            let ship = scale_outline(&shapes.ship, scale);
+            return self.outline(&ship, player.angle, pos);
+        }
+        let player_pos = interpolate_pos(player.prev_pos, player.pos, alpha, viewport);
+        let player_angle = interpolate_angle(player.prev_angle, player.angle, alpha);
+        let blink_on = match world.invulnerable_elapsed() {
+            Some(elapsed) => (elapsed.as_millis() / 100).is_multiple_of(2),
+            None => true,
+        };
+        if blink_on {
+            self.outline(&shapes.ship, player_angle, player_pos)?;
+            if player.thrusting {
+                self.outline(&shapes.ship_thrust, player_angle, player_pos)?;
+            }
+        }
+        if player.shielding {
+            let radius = world.config().shield.radius;
+            self.outline(&circle(radius), 0.0, player_pos)?;
+        }
+        Ok(())
+    }
+
+    /// The ship's outline breaking apart, each edge drifting away from the
+    /// centre and spinning until it fades out.
+    fn explosion(
+        &mut self,
+        outline: &[Vec2],
+        angle: f32,
+        pos: Vec2,
+        since: Duration,
+    ) -> Result<(), String> {
+        let t = since.as_secs_f32();
+        if since >= EXPLOSION_TIME {
+            return Ok(());
+        }
+        let outline = rotate(outline, angle);
+        for (i, edge) in outline.windows(2).enumerate() {
+            let mid = ((edge[0].0 + edge[1].0) / 2.0, (edge[0].1 + edge[1].1) / 2.0);
+            let len = (mid.0 * mid.0 + mid.1 * mid.1).sqrt().max(1.0);
+            let drift = (
+                mid.0 / len * DEBRIS_SPEED * t,
+                mid.1 / len * DEBRIS_SPEED * t,
+            );
+            let spin = if i % 2 == 0 {
+                DEBRIS_SPIN
+            } else {
+                -DEBRIS_SPIN
+            } * t;
+            // rotate the edge around its own middle, then push it outwards
+            let local = [
+                (edge[0].0 - mid.0, edge[0].1 - mid.1),
+                (edge[1].0 - mid.0, edge[1].1 - mid.1),
+            ];
+            let center = (pos.0 + mid.0 + drift.0, pos.1 + mid.1 + drift.1);
+            self.outline(&local, spin, center)?;
+        }
+        Ok(())
+    }
+
+    /// Score in the top right, lives in the top left, wave banner in the middle.
+    pub fn hud(&mut self, world: &World) -> Result<(), String> {
+        let margin = 12;
+        let score = world.player.score.to_string();
+        self.text(
+            &score,
+            FontSize::Medium,
+            (world.viewport.0 as i32 - margin, margin - 10),
+            Align::Right,
+            WHITE,
+        )?;
+
+        // waiting to come back, show where once the centre is still busy
+        let (w, h) = world.viewport;
+        let center = (w as f32 / 2.0, h as f32 / 2.0);
+        let since_death = world.since_death().filter(|_| world.player.health > 0);
+        if let Some(since) = since_death {
+            self.text(
+                "READY",
+                FontSize::Medium,
+                (center.0 as i32, center.1 as i32 + 40),
+                Align::Center,
+                WHITE,
+            )?;
+            if world.respawn_blocked() && since.as_millis() % 500 < 250 {
+                self.canvas.set_draw_color(GREY);
+                self.outline(&world.shapes().ship, 0.0, center)?;
+                self.canvas.set_draw_color(WHITE);
+            }
+        }
+
+        // announce the next wave while the field is empty
+        if let Some(wave) = world.upcoming_wave() {
+            self.text(
+                &format!("WAVE {}", wave),
+                FontSize::Large,
+                (w as i32 / 2, h as i32 / 3),
+                Align::Center,
+                WHITE,
+            )?;
+        }
+
+        // the newest life blinks for a moment after it was awarded
+        let flashing = world
+            .since_extra_life()
+            .filter(|&since| since < EXTRA_LIFE_FLASH);
+        let blink_off = flashing.is_some_and(|since| !(since.as_millis() / 120).is_multiple_of(2));
+        let hud_margin = 36.0_f32;
+        let hud_spacing = 36.0_f32;
+        for i in 0..world.player.health {
+            if blink_off && i == world.player.health - 1 {
+                continue;
+            }
+            let x = hud_margin + i as f32 * hud_spacing;
+            let y = hud_margin;
+            self.outline(&world.shapes().ship, 0.0, (x, y))?;
+        }
+        if flashing.is_some() {
+            let x = (hud_margin + world.player.health as f32 * hud_spacing) as i32;
+            self.text("extra life", FontSize::Small, (x, 20), Align::Left, WHITE)?;
+        }
+
+        // shield energy under the lives, grey once it's empty
+        let (bar_w, bar_h) = ENERGY_BAR;
+        let bar = Rect::new(hud_margin as i32 - 16, hud_margin as i32 + 24, bar_w, bar_h);
+        let energy = world.player.shield_energy;
+        let fill = (bar_w as f32 * energy).round() as u32;
+        self.canvas
+            .set_draw_color(if energy > 0.0 { WHITE } else { GREY });
+        self.canvas.draw_rect(bar)?;
+        if fill > 0 {
+            self.canvas
+                .fill_rect(Rect::new(bar.x(), bar.y(), fill, bar_h))?;
+        }
+        self.canvas.set_draw_color(WHITE);
+        self.text(
+            world.weapon().name(),
+            FontSize::Small,
+            (bar.x(), bar.bottom() + 4),
+            Align::Left,
+            GREY,
+        )?;
+
+        // timed power-ups under the score, with the seconds they have left
+        let radius = world.config().powerups.radius;
+        let x = w as i32 - margin - radius as i32;
+        for (i, (kind, left)) in world.effects().enumerate() {
+            let y = 80 + i as i32 * (2 * radius as i32 + 12);
+            self.powerup(world, kind, (x as f32, y as f32))?;
+            self.text(
+                &left.as_secs_f32().ceil().to_string(),
+                FontSize::Small,
+                (x - radius as i32 - 10, y - self.small.height() / 2),
+                Align::Right,
+                WHITE,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// closed loop of points around the origin
+fn circle(radius: f32) -> Vec<Vec2> {
+    (0..=CIRCLE_SEGMENTS)
+        .map(|i| {
+            let angle = i as f32 / CIRCLE_SEGMENTS as f32 * std::f32::consts::TAU;
+            (radius * angle.cos(), radius * angle.sin())
+        })
+        .collect()
+}
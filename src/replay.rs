@@ -0,0 +1,444 @@
+use crate::config::GameConfig;
+use crate::shapes::Shapes;
+use crate::world::{GameMode, InputState};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+// file layout (all little endian):
+//   "BLRP" | version u8 | seed u64 | viewport w u32 | h u32 | mode flags u8 (since v2)
+//   | config toml length u32 | config toml utf8 (since v3)
+//   | shapes toml length u32 | shapes toml utf8, empty when unknown (since v13)
+//   then records until eof:
+//     input flags u8 (< RESIZE) | tick count u16   -> same input for that many ticks
+//     RESIZE u8 | w u32 | h u32                    -> viewport changed before the next tick
+//     SWITCH_WEAPON u8                             -> weapon switched before the next tick (since v12)
+const MAGIC: &[u8; 4] = b"BLRP";
+const VERSION: u8 = 15;
+// v1 and v2 files were recorded before and after the small asteroid tier came
+// in with nothing to tell them apart, so neither can be played back, and v3
+// spans runs starting over at zero lives and runs stopping at game over
+const OLDEST_VERSION: u8 = 4;
+
+const THRUST: u8 = 1 << 0;
+const TURN_LEFT: u8 = 1 << 1;
+const TURN_RIGHT: u8 = 1 << 2;
+const FIRE: u8 = 1 << 3;
+const HYPERSPACE: u8 = 1 << 4; // since v7
+const SHIELD: u8 = 1 << 5; // since v8
+const FIRE_HELD: u8 = 1 << 6; // since v11
+const RESIZE: u8 = 1 << 7;
+const SWITCH_WEAPON: u8 = RESIZE | 1;
+
+const POLYGON_COLLISIONS: u8 = 1 << 0;
+const ASTEROID_BOUNCES: u8 = 1 << 1;
+const PROCEDURAL_ASTEROIDS: u8 = 1 << 2;
+const ENDLESS: u8 = 1 << 3; // since v4
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ReplayEntry {
+    Tick(InputState),
+    Resize((u32, u32)),
+    SwitchWeapon,
+}
+
+/// Everything needed to play a run back: the seed, the starting viewport, the
+/// game mode, the tuning config, the outlines and the input of every tick.
+pub struct Replay {
+    pub seed: u64,
+    pub viewport: (u32, u32),
+    pub mode: GameMode,
+    pub config: GameConfig,
+    /// `None` for replays older than v13, they play back with whatever is loaded.
+    pub shapes: Option<Shapes>,
+    pub entries: Vec<ReplayEntry>,
+}
+
+fn input_to_flags(input: &InputState) -> u8 {
+    let mut flags = 0;
+    if input.thrust {
+        flags |= THRUST;
+    }
+    if input.turn_left {
+        flags |= TURN_LEFT;
+    }
+    if input.turn_right {
+        flags |= TURN_RIGHT;
+    }
+    if input.fire {
+        flags |= FIRE;
+    }
+    if input.hyperspace {
+        flags |= HYPERSPACE;
+    }
+    if input.shield {
+        flags |= SHIELD;
+    }
+    if input.fire_held {
+        flags |= FIRE_HELD;
+    }
+    flags
+}
+
+fn flags_to_input(flags: u8) -> InputState {
+    InputState {
+        thrust: flags & THRUST != 0,
+        turn_left: flags & TURN_LEFT != 0,
+        turn_right: flags & TURN_RIGHT != 0,
+        fire: flags & FIRE != 0,
+        fire_held: flags & FIRE_HELD != 0,
+        hyperspace: flags & HYPERSPACE != 0,
+        shield: flags & SHIELD != 0,
+    }
+}
+
+fn mode_to_flags(mode: &GameMode) -> u8 {
+    let mut flags = 0;
+    if mode.polygon_collisions {
+        flags |= POLYGON_COLLISIONS;
+    }
+    if mode.asteroid_bounces {
+        flags |= ASTEROID_BOUNCES;
+    }
+    if mode.procedural_asteroids {
+        flags |= PROCEDURAL_ASTEROIDS;
+    }
+    if mode.endless {
+        flags |= ENDLESS;
+    }
+    flags
+}
+
+fn flags_to_mode(flags: u8) -> GameMode {
+    GameMode {
+        polygon_collisions: flags & POLYGON_COLLISIONS != 0,
+        asteroid_bounces: flags & ASTEROID_BOUNCES != 0,
+        procedural_asteroids: flags & PROCEDURAL_ASTEROIDS != 0,
+        endless: flags & ENDLESS != 0,
+    }
+}
+
+// length prefixed utf8 text
+fn read_text(reader: &mut impl Read) -> Result<String, String> {
+    let len = u32::from_le_bytes(read_bytes(reader)?) as usize;
+    let mut text = vec![0; len];
+    reader
+        .read_exact(&mut text)
+        .map_err(|err| format!("Replay file is truncated: {}", err))?;
+    String::from_utf8(text).map_err(|_| "Replay file is corrupt".to_string())
+}
+
+fn write_text(writer: &mut impl Write, text: &str) -> std::io::Result<()> {
+    writer.write_all(&(text.len() as u32).to_le_bytes())?;
+    writer.write_all(text.as_bytes())
+}
+
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> Result<[u8; N], String> {
+    let mut buf = [0; N];
+    reader
+        .read_exact(&mut buf)
+        .map_err(|err| format!("Replay file is truncated: {}", err))?;
+    Ok(buf)
+}
+
+impl Replay {
+    pub fn new(
+        seed: u64,
+        viewport: (u32, u32),
+        mode: GameMode,
+        config: GameConfig,
+        shapes: Shapes,
+    ) -> Self {
+        Replay {
+            seed,
+            viewport,
+            mode,
+            config,
+            shapes: Some(shapes),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push_input(&mut self, input: &InputState) {
+        self.entries.push(ReplayEntry::Tick(*input));
+    }
+
+    pub fn push_resize(&mut self, viewport: (u32, u32)) {
+        self.entries.push(ReplayEntry::Resize(viewport));
+    }
+
+    pub fn push_switch_weapon(&mut self) {
+        self.entries.push(ReplayEntry::SwitchWeapon);
+    }
+
+    pub fn write_to(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&self.seed.to_le_bytes())?;
+        writer.write_all(&self.viewport.0.to_le_bytes())?;
+        writer.write_all(&self.viewport.1.to_le_bytes())?;
+        writer.write_all(&[mode_to_flags(&self.mode)])?;
+        write_text(writer, &self.config.to_toml())?;
+        let shapes = self.shapes.as_ref().map(Shapes::to_toml);
+        write_text(writer, shapes.as_deref().unwrap_or_default())?;
+
+        // run length encode, input usually stays the same for many ticks
+        let mut run: Option<(u8, u16)> = None;
+        for entry in &self.entries {
+            match entry {
+                ReplayEntry::Tick(input) => {
+                    let flags = input_to_flags(input);
+                    run = match run {
+                        Some((f, count)) if f == flags && count < u16::MAX => Some((f, count + 1)),
+                        Some((f, count)) => {
+                            writer.write_all(&[f])?;
+                            writer.write_all(&count.to_le_bytes())?;
+                            Some((flags, 1))
+                        }
+                        None => Some((flags, 1)),
+                    };
+                }
+                ReplayEntry::Resize((w, h)) => {
+                    if let Some((f, count)) = run.take() {
+                        writer.write_all(&[f])?;
+                        writer.write_all(&count.to_le_bytes())?;
+                    }
+                    writer.write_all(&[RESIZE])?;
+                    writer.write_all(&w.to_le_bytes())?;
+                    writer.write_all(&h.to_le_bytes())?;
+                }
+                ReplayEntry::SwitchWeapon => {
+                    if let Some((f, count)) = run.take() {
+                        writer.write_all(&[f])?;
+                        writer.write_all(&count.to_le_bytes())?;
+                    }
+                    writer.write_all(&[SWITCH_WEAPON])?;
+                }
+            }
+        }
+        if let Some((f, count)) = run {
+            writer.write_all(&[f])?;
+            writer.write_all(&count.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn read_from(reader: &mut impl Read) -> Result<Self, String> {
+        let magic = read_bytes::<4>(reader)?;
+        if &magic != MAGIC {
+            return Err("Not a replay file".to_string());
+        }
+        let [version] = read_bytes::<1>(reader)?;
+        if !(OLDEST_VERSION..=VERSION).contains(&version) {
+            return Err(format!(
+                "Unsupported replay version {} (expected {} to {})",
+                version, OLDEST_VERSION, VERSION
+            ));
+        }
+        // rapid fire and spread stacked in v11, one weapon at a time can't replay that
+        if version == 11 {
+            return Err("Unsupported replay version 11, its power-ups worked differently".into());
+        }
+        let seed = u64::from_le_bytes(read_bytes(reader)?);
+        let w = u32::from_le_bytes(read_bytes(reader)?);
+        let h = u32::from_le_bytes(read_bytes(reader)?);
+        let mode = flags_to_mode(read_bytes::<1>(reader)?[0]);
+        let mut config = GameConfig::parse(&read_text(reader)?)
+            .map_err(|err| format!("Replay config: {}", err))?;
+        // extra lives came in v5, the defaults would hand out lives the run never had
+        if version < 5 {
+            config.lives.extra_life_first = 0;
+        }
+        // and before v6 a hit never cost the ship, it just flashed
+        if version < 6 {
+            config.ship.explode_on_hit = false;
+        }
+        // saucers only showed up in v9
+        if version < 9 {
+            config.saucers.enabled = false;
+        }
+        // and until v10 small ones aimed dead at the ship
+        if version < 10 {
+            config.saucers.lead_targeting = false;
+            config.saucers.aim_error_easy = 0.0;
+            config.saucers.aim_error_hard = 0.0;
+        }
+        // nothing dropped power-ups before v11
+        if version < 11 {
+            config.powerups.drop_chance = 0.0;
+        }
+        // outlines are only embedded since v13
+        let shapes = if version < 13 {
+            String::new()
+        } else {
+            read_text(reader)?
+        };
+        let shapes = match shapes.as_str() {
+            "" => None,
+            text => Some(Shapes::parse(text).map_err(|err| format!("Replay shapes: {}", err))?),
+        };
+        let mut replay = Replay {
+            seed,
+            viewport: (w, h),
+            mode,
+            config,
+            shapes,
+            entries: Vec::new(),
+        };
+
+        loop {
+            let mut tag = [0];
+            match reader.read(&mut tag) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(err) => return Err(format!("Failed to read replay: {}", err)),
+            }
+            if tag[0] == RESIZE {
+                let w = u32::from_le_bytes(read_bytes(reader)?);
+                let h = u32::from_le_bytes(read_bytes(reader)?);
+                replay.push_resize((w, h));
+            } else if tag[0] == SWITCH_WEAPON {
+                // piercing shots broke every fragment of the first rock they hit before
+                // v14, and went through its sibling fragments in v14
+                if version < 15 {
+                    return Err(format!(
+                        "Unsupported replay version {}, it switched to a weapon that works differently now",
+                        version
+                    ));
+                }
+                replay.push_switch_weapon();
+            } else if tag[0]
+                & !(THRUST | TURN_LEFT | TURN_RIGHT | FIRE | HYPERSPACE | SHIELD | FIRE_HELD)
+                == 0
+            {
+                let count = u16::from_le_bytes(read_bytes(reader)?);
+                let input = flags_to_input(tag[0]);
+                for _ in 0..count {
+                    replay.push_input(&input);
+                }
+            } else {
+                return Err(format!("Corrupt replay record {:#04x}", tag[0]));
+            }
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let file = File::create(path)
+            .map_err(|err| format!("Failed to create {}: {}", path.display(), err))?;
+        let mut writer = BufWriter::new(file);
+        self.write_to(&mut writer)
+            .and_then(|_| writer.flush())
+            .map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = File::open(path)
+            .map_err(|err| format!("Failed to open {}: {}", path.display(), err))?;
+        Self::read_from(&mut BufReader::new(file))
+            .map_err(|err| format!("{}: {}", path.display(), err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Replay {
+        let mode = GameMode {
+            polygon_collisions: true,
+            endless: true,
+            ..GameMode::default()
+        };
+        let mut config = GameConfig::default();
+        config.lives.starting = 4;
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/shapes.toml");
+        let shapes = Shapes::load(&path).unwrap();
+        let mut replay = Replay::new(0xdead_beef, (1024, 768), mode, config, shapes);
+        let fire = InputState {
+            fire: true,
+            thrust: true,
+            ..InputState::default()
+        };
+        replay.push_input(&InputState::default());
+        replay.push_input(&fire);
+        replay.push_input(&fire);
+        replay.push_resize((640, 480));
+        replay.push_input(&fire);
+        replay.push_switch_weapon();
+        replay.push_input(&InputState {
+            shield: true,
+            fire_held: true,
+            hyperspace: true,
+            ..InputState::default()
+        });
+        replay
+    }
+
+    fn round_trip(replay: &Replay) -> Replay {
+        let mut bytes = Vec::new();
+        replay.write_to(&mut bytes).unwrap();
+        Replay::read_from(&mut bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn write_then_read_gives_the_same_replay() {
+        let replay = sample();
+        let read = round_trip(&replay);
+        assert_eq!(read.seed, replay.seed);
+        assert_eq!(read.viewport, replay.viewport);
+        assert_eq!(read.mode, replay.mode);
+        assert_eq!(read.config.to_toml(), replay.config.to_toml());
+        assert_eq!(read.shapes, replay.shapes);
+        assert_eq!(read.entries, replay.entries);
+    }
+
+    #[test]
+    fn runs_longer_than_a_record_are_split() {
+        let mut replay = sample();
+        replay.entries.clear();
+        for _ in 0..u16::MAX as usize + 10 {
+            replay.push_input(&InputState::default());
+        }
+        assert_eq!(round_trip(&replay).entries, replay.entries);
+    }
+
+    #[test]
+    fn rejects_versions_that_played_differently() {
+        let mut bytes = Vec::new();
+        sample().write_to(&mut bytes).unwrap();
+        for version in [1, 2, 3, 11] {
+            bytes[4] = version;
+            let err = Replay::read_from(&mut bytes.as_slice()).err().unwrap();
+            assert!(err.contains("Unsupported replay version"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn rejects_weapon_switches_from_before_piercing_went_through() {
+        let mut bytes = Vec::new();
+        sample().write_to(&mut bytes).unwrap();
+        for version in [13, 14] {
+            bytes[4] = version;
+            let err = Replay::read_from(&mut bytes.as_slice()).err().unwrap();
+            assert!(err.contains("Unsupported replay version"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn rejects_other_files_and_newer_versions() {
+        assert!(Replay::read_from(&mut b"PNG!\x01".as_slice()).is_err());
+
+        let mut bytes = Vec::new();
+        sample().write_to(&mut bytes).unwrap();
+        bytes[4] = VERSION + 1;
+        let err = Replay::read_from(&mut bytes.as_slice()).err().unwrap();
+        assert!(err.contains("Unsupported replay version"), "{}", err);
+
+        bytes[4] = VERSION;
+        bytes.push(0x90);
+        assert!(Replay::read_from(&mut bytes.as_slice()).is_err());
+    }
+}
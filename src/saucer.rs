@@ -0,0 +1,94 @@
+use crate::config::SaucerConfig;
+use crate::geometry::{outline_radius, scale_outline, translate, Vec2};
+use rand::Rng;
+use std::time::Duration;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SaucerSize {
+    Large,
+    Small,
+}
+
+/// A flying saucer crossing the screen, it leaves once it reaches the other side.
+pub struct Saucer {
+    pub pos: Vec2,
+    pub vel: Vec2, // px per second
+    pub prev_pos: Vec2,
+    pub shape: Vec<Vec2>,
+    pub radius: f32,
+    pub size: SaucerSize,
+    pub next_turn: Duration,
+    pub next_shot: Duration,
+    pub id: u32,
+}
+
+impl Saucer {
+    /// Outline moved to where the saucer is now, saucers never rotate.
+    pub fn world_outline(&self) -> Vec<Vec2> {
+        translate(&self.shape, self.pos)
+    }
+
+    /// Past the side it was heading for.
+    pub fn has_left(&self, viewport: (u32, u32)) -> bool {
+        let margin = self.radius;
+        if self.vel.0 > 0.0 {
+            self.pos.0 > viewport.0 as f32 + margin
+        } else {
+            self.pos.0 < -margin
+        }
+    }
+
+    /// Keep crossing, but pick a new diagonal (or straight) course.
+    pub fn turn(&mut self, config: &SaucerConfig, rng: &mut impl Rng) {
+        let speed = config.size(self.size).speed;
+        self.vel.1 = speed * [-0.5, 0.0, 0.5][rng.gen_range(0..3)];
+    }
+}
+
+/// Small saucers get more likely the higher the score.
+pub fn pick_saucer_size(rng: &mut impl Rng, score: i32, config: &SaucerConfig) -> SaucerSize {
+    if rng.gen::<f32>() < config.small_chance(score) {
+        SaucerSize::Small
+    } else {
+        SaucerSize::Large
+    }
+}
+
+/// A saucer just off the left or right edge, heading across.
+pub fn spawn_saucer(
+    base_shape: &[Vec2],
+    size: SaucerSize,
+    viewport: (u32, u32),
+    time: Duration,
+    config: &SaucerConfig,
+    rng: &mut impl Rng,
+) -> Saucer {
+    let rules = config.size(size);
+    let shape = scale_outline(base_shape, rules.scale);
+    let radius = outline_radius(&shape);
+    let (w, h) = (viewport.0 as f32, viewport.1 as f32);
+    let (x, dir) = if rng.gen() {
+        (-radius, 1.0)
+    } else {
+        (w + radius, -1.0)
+    };
+    // keep clear of the top and bottom edge where it would wrap right away
+    let y = if h > 4.0 * radius {
+        rng.gen_range(2.0 * radius..h - 2.0 * radius)
+    } else {
+        h / 2.0
+    };
+    let mut saucer = Saucer {
+        pos: (x, y),
+        vel: (rules.speed * dir, 0.0),
+        prev_pos: (x, y),
+        shape,
+        radius,
+        size,
+        next_turn: time + config.turn_interval(),
+        next_shot: time + config.fire_interval(),
+        id: 0,
+    };
+    saucer.turn(config, rng);
+    saucer
+}
@@ -0,0 +1,123 @@
+use crate::geometry::{scale_outline, Vec2};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct OutlineFile {
+    points: Vec<[f32; 2]>,
+    #[serde(default = "default_scale")]
+    scale: f32,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ShapesFile {
+    ship: OutlineFile,
+    ship_thrust: OutlineFile,
+    projectile: OutlineFile,
+    saucer: OutlineFile,
+    asteroids: BTreeMap<String, OutlineFile>,
+}
+
+/// Every outline the game draws, already scaled.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Shapes {
+    pub ship: Vec<Vec2>,
+    pub ship_thrust: Vec<Vec2>,
+    pub projectile: Vec<Vec2>,
+    pub saucer: Vec<Vec2>,
+    pub asteroids: Vec<Vec<Vec2>>, // sorted by name
+}
+
+// check and scale one outline, `name` is only used for error messages
+fn load_outline(name: &str, outline: OutlineFile, closed: bool) -> Result<Vec<Vec2>, String> {
+    let points: Vec<Vec2> = outline.points.iter().map(|p| (p[0], p[1])).collect();
+    if !outline.scale.is_finite() || outline.scale <= 0.0 {
+        return Err(format!("{}: scale must be a positive number", name));
+    }
+    if points.iter().any(|p| !p.0.is_finite() || !p.1.is_finite()) {
+        return Err(format!("{}: points must be finite numbers", name));
+    }
+    if closed {
+        if points.len() < 4 {
+            return Err(format!(
+                "{}: a closed outline needs at least 3 corners plus the closing point",
+                name
+            ));
+        }
+        if points.first() != points.last() {
+            return Err(format!("{}: outline must end on its first point", name));
+        }
+    } else if points.len() < 2 {
+        return Err(format!("{}: an outline needs at least 2 points", name));
+    }
+    Ok(scale_outline(&points, outline.scale))
+}
+
+impl Shapes {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let file: ShapesFile = toml::from_str(text).map_err(|err| err.to_string())?;
+        if file.asteroids.is_empty() {
+            return Err("asteroids: at least one asteroid outline is needed".to_string());
+        }
+        let asteroids = file
+            .asteroids
+            .into_iter()
+            .map(|(name, outline)| load_outline(&format!("asteroids.{}", name), outline, true))
+            .collect::<Result<_, _>>()?;
+        Ok(Shapes {
+            ship: load_outline("ship", file.ship, true)?,
+            ship_thrust: load_outline("ship_thrust", file.ship_thrust, true)?,
+            projectile: load_outline("projectile", file.projectile, false)?,
+            saucer: load_outline("saucer", file.saucer, true)?,
+            asteroids,
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+        Self::parse(&text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// Shapes file that parses back to exactly these outlines. Scales are
+    /// already applied and asteroid names are replaced by their index.
+    pub fn to_toml(&self) -> String {
+        let outline = |points: &[Vec2]| OutlineFile {
+            points: points.iter().map(|&(x, y)| [x, y]).collect(),
+            scale: 1.0,
+        };
+        // zero padded so the names sort in the same order
+        let width = self.asteroids.len().to_string().len();
+        let file = ShapesFile {
+            ship: outline(&self.ship),
+            ship_thrust: outline(&self.ship_thrust),
+            projectile: outline(&self.projectile),
+            saucer: outline(&self.saucer),
+            asteroids: self
+                .asteroids
+                .iter()
+                .enumerate()
+                .map(|(i, points)| (format!("{:0width$}", i), outline(points)))
+                .collect(),
+        };
+        toml::to_string(&file).expect("shapes are always valid toml")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_toml_parses_back_to_the_same_shapes() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/shapes.toml");
+        let shapes = Shapes::load(&path).unwrap();
+        assert_eq!(Shapes::parse(&shapes.to_toml()).unwrap(), shapes);
+    }
+}
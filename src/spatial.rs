@@ -0,0 +1,106 @@
+use crate::geometry::Vec2;
+
+/// Uniform grid broad phase. Cells wrap around the viewport the same way
+/// `wrap_position` does, so things just off one edge share cells with things
+/// on the opposite edge and nothing ever falls outside the grid.
+pub struct SpatialHash {
+    cell_size: f32,
+    cols: i32,
+    rows: i32,
+    cells: Vec<Vec<usize>>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        SpatialHash {
+            cell_size,
+            cols: 1,
+            rows: 1,
+            cells: vec![Vec::new()],
+        }
+    }
+
+    /// Clear and re-insert every circle, `items` index becomes the id returned by `query`.
+    pub fn rebuild(&mut self, viewport: (u32, u32), items: impl Iterator<Item = (Vec2, f32)>) {
+        self.cols = ((viewport.0 as f32 / self.cell_size).ceil() as i32).max(1);
+        self.rows = ((viewport.1 as f32 / self.cell_size).ceil() as i32).max(1);
+        let cell_count = (self.cols * self.rows) as usize;
+        for cell in self.cells.iter_mut() {
+            cell.clear();
+        }
+        self.cells.resize_with(cell_count, Vec::new);
+
+        for (id, (pos, radius)) in items.enumerate() {
+            for cell in covered_cells(self.cell_size, (self.cols, self.rows), pos, radius) {
+                self.cells[cell].push(id);
+            }
+        }
+    }
+
+    /// Ids of every circle that may overlap the given one, sorted and without duplicates.
+    pub fn query(&self, pos: Vec2, radius: f32, out: &mut Vec<usize>) {
+        out.clear();
+        for cell in covered_cells(self.cell_size, (self.cols, self.rows), pos, radius) {
+            out.extend_from_slice(&self.cells[cell]);
+        }
+        out.sort_unstable();
+        out.dedup();
+    }
+}
+
+// cells touched by the circle's bounding box, wrapped onto the grid
+fn covered_cells(
+    cell_size: f32,
+    (cols, rows): (i32, i32),
+    pos: Vec2,
+    radius: f32,
+) -> impl Iterator<Item = usize> {
+    let span = move |min: f32, max: f32, count: i32| {
+        let first = (min / cell_size).floor() as i32;
+        let last = (max / cell_size).floor() as i32;
+        // a huge circle covers the whole axis, don't visit cells twice
+        let len = (last - first + 1).min(count);
+        (first..first + len).map(move |c| c.rem_euclid(count))
+    };
+    span(pos.0 - radius, pos.0 + radius, cols).flat_map(move |x| {
+        span(pos.1 - radius, pos.1 + radius, rows).map(move |y| (y * cols + x) as usize)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(grid: &SpatialHash, pos: Vec2, radius: f32) -> Vec<usize> {
+        let mut out = Vec::new();
+        grid.query(pos, radius, &mut out);
+        out
+    }
+
+    #[test]
+    fn finds_neighbours_across_the_wrap() {
+        let mut grid = SpatialHash::new(64.0);
+        let items = [
+            ((5.0, 300.0), 10.0),   // 0: left edge
+            ((795.0, 300.0), 10.0), // 1: right edge, same row
+            ((400.0, 5.0), 10.0),   // 2: top edge
+            ((400.0, 300.0), 10.0), // 3: middle
+            ((-20.0, 610.0), 10.0), // 4: off the bottom left corner
+        ];
+        grid.rebuild((800, 600), items.into_iter());
+
+        assert!(query(&grid, (795.0, 300.0), 10.0).contains(&0));
+        assert!(query(&grid, (5.0, 300.0), 10.0).contains(&1));
+        assert!(query(&grid, (400.0, 595.0), 10.0).contains(&2));
+        assert!(query(&grid, (790.0, 5.0), 10.0).contains(&4));
+        assert!(!query(&grid, (5.0, 300.0), 10.0).contains(&3));
+    }
+
+    #[test]
+    fn huge_circles_are_reported_once() {
+        let mut grid = SpatialHash::new(64.0);
+        grid.rebuild((200, 200), [((100.0, 100.0), 500.0)].into_iter());
+        assert_eq!(query(&grid, (10.0, 10.0), 1.0), vec![0]);
+        assert_eq!(query(&grid, (100.0, 100.0), 1000.0), vec![0]);
+    }
+}
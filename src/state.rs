@@ -0,0 +1,258 @@
+mod game_over;
+mod initials;
+mod paused;
+mod playing;
+mod title;
+
+pub use game_over::GameOver;
+pub use initials::EnterInitials;
+pub use paused::Paused;
+pub use playing::Playing;
+pub use title::Title;
+
+use crate::{
+    audio::Sounds,
+    cli::{Options, DEFAULT_CONFIG},
+    render::Renderer,
+};
+use blasteroids::{
+    config::GameConfig,
+    highscores::{HighScore, HighScores},
+    replay::{Replay, ReplayEntry},
+    shapes::Shapes,
+    world::{GameMode, InputState, World},
+};
+use sdl2::event::Event;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+// screens after a run ignore keys for a moment, fire is probably still being hammered
+const INPUT_DELAY: Duration = Duration::from_millis(1000);
+
+// built in defaults when there is no config.toml, but a file asked for
+// with --config has to be there
+pub fn load_config(path: &Path) -> Result<GameConfig, String> {
+    if path == Path::new(DEFAULT_CONFIG) && !path.exists() {
+        Ok(GameConfig::default())
+    } else {
+        GameConfig::load(path)
+    }
+}
+
+/// Everything that outlives a single screen.
+pub struct App<'ttf> {
+    pub options: Options,
+    pub mode: GameMode,
+    pub config: GameConfig, // picked up by the next run, F5 also swaps it into the current one
+    pub shapes: Shapes,
+    pub sounds: Sounds,
+    pub renderer: Renderer<'ttf>,
+    pub highscores: HighScores,
+    pub highscores_path: Option<PathBuf>, // None when the table couldn't be read, don't clobber it
+}
+
+impl App<'_> {
+    /// Put a finished run on the table, returns its rank if it made it.
+    pub fn submit_score(&mut self, entry: HighScore) -> Option<usize> {
+        let rank = self.highscores.insert(entry)?;
+        if let Some(path) = &self.highscores_path {
+            // losing the table is not worth crashing over
+            if let Err(err) = self.highscores.save(path) {
+                eprintln!("{}", err);
+            }
+        }
+        Some(rank)
+    }
+}
+
+/// One game from the first asteroid to the last life, plus its recording or playback.
+pub struct Run {
+    pub world: World,
+    pub input: InputState,
+    recording: Option<Replay>,
+    playback: Option<std::vec::IntoIter<ReplayEntry>>,
+}
+
+impl Run {
+    pub fn new(app: &App) -> Self {
+        let seed = app.options.seed.unwrap_or_else(rand::random);
+        println!("seed: {}", seed);
+        let viewport = app.renderer.size();
+        let world = World::new(
+            viewport,
+            seed,
+            app.mode,
+            app.shapes.clone(),
+            app.config.clone(),
+        );
+        let recording = app.options.record.as_ref().map(|_| {
+            let (config, shapes) = (app.config.clone(), app.shapes.clone());
+            Replay::new(seed, viewport, app.mode, config, shapes)
+        });
+        Run {
+            world,
+            input: InputState::default(),
+            recording,
+            playback: None,
+        }
+    }
+
+    pub fn from_replay(replay: Replay, app: &App) -> Self {
+        println!("seed: {}", replay.seed);
+        let shapes = replay.shapes.unwrap_or_else(|| {
+            eprintln!("replay has no outlines of its own, it may not play back exactly");
+            app.shapes.clone()
+        });
+        let world = World::new(
+            replay.viewport,
+            replay.seed,
+            replay.mode,
+            shapes,
+            replay.config,
+        );
+        Run {
+            world,
+            input: InputState::default(),
+            recording: None,
+            playback: Some(replay.entries.into_iter()),
+        }
+    }
+
+    // live tuning and resizes would break exact playback
+    pub fn is_replay(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    pub fn resize(&mut self, viewport: (u32, u32)) {
+        // a replay only follows the recorded viewport
+        if self.is_replay() {
+            return;
+        }
+        self.world.resize(viewport);
+        if let Some(recording) = self.recording.as_mut() {
+            recording.push_resize(viewport);
+        }
+    }
+
+    pub fn switch_weapon(&mut self) {
+        // a replay switches when the recording did
+        if self.is_replay() {
+            return;
+        }
+        self.world.switch_weapon();
+        if let Some(recording) = self.recording.as_mut() {
+            recording.push_switch_weapon();
+        }
+    }
+
+    /// One fixed tick, false once a replay has run out of input.
+    pub fn step(&mut self, app: &mut App) -> Result<bool, String> {
+        if let Some(entries) = self.playback.as_mut() {
+            loop {
+                match entries.next() {
+                    Some(ReplayEntry::Resize((w, h))) => {
+                        self.world.resize((w, h));
+                        app.renderer
+                            .canvas
+                            .window_mut()
+                            .set_size(w, h)
+                            .map_err(|err| err.to_string())?;
+                    }
+                    Some(ReplayEntry::SwitchWeapon) => self.world.switch_weapon(),
+                    Some(ReplayEntry::Tick(recorded)) => {
+                        self.input = recorded;
+                        break;
+                    }
+                    None => {
+                        println!("replay finished");
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+        if let Some(recording) = self.recording.as_mut() {
+            recording.push_input(&self.input);
+        }
+        self.world.step(&self.input);
+        self.input.fire = false;
+        self.input.hyperspace = false;
+
+        for event in self.world.drain_events() {
+            app.sounds.play(event);
+        }
+        Ok(true)
+    }
+
+    /// Save the recording if one was asked for, every run overwrites the last.
+    pub fn finish(self, app: &App) -> Result<World, String> {
+        if let (Some(recording), Some(path)) = (&self.recording, &app.options.record) {
+            recording.save(path)?;
+            println!("replay saved to {}", path.display());
+        }
+        Ok(self.world)
+    }
+}
+
+/// Which screen is showing. Each one handles its own input and drawing and
+/// hands back the state to continue with.
+pub enum State {
+    Title(Title),
+    Playing(Playing),
+    Paused(Paused),
+    EnterInitials(EnterInitials),
+    GameOver(GameOver),
+    Quit,
+}
+
+impl State {
+    pub fn handle_event(self, event: &Event, app: &mut App) -> Result<State, String> {
+        match self {
+            State::Title(title) => title.handle_event(event, app),
+            State::Playing(playing) => playing.handle_event(event, app),
+            State::Paused(paused) => paused.handle_event(event, app),
+            State::EnterInitials(initials) => initials.handle_event(event, app),
+            State::GameOver(game_over) => game_over.handle_event(event, app),
+            State::Quit => Ok(State::Quit),
+        }
+    }
+
+    /// Advance by one fixed `TICK`, only the game itself moves.
+    pub fn tick(self, app: &mut App) -> Result<State, String> {
+        match self {
+            State::Playing(playing) => playing.tick(app),
+            state => Ok(state),
+        }
+    }
+
+    pub fn draw(&self, app: &mut App, alpha: f32) -> Result<(), String> {
+        match self {
+            State::Title(title) => title.draw(app),
+            State::Playing(playing) => playing.draw(app, alpha),
+            State::Paused(paused) => paused.draw(app),
+            State::EnterInitials(initials) => initials.draw(app),
+            State::GameOver(game_over) => game_over.draw(app),
+            State::Quit => Ok(()),
+        }
+    }
+
+    /// The window is closing, don't lose a run in progress.
+    pub fn quit(self, app: &mut App) -> Result<(), String> {
+        match self {
+            State::Playing(Playing { run }) | State::Paused(Paused { run }) => {
+                run.finish(app)?;
+            }
+            // keep the score even if the name is only half done
+            State::EnterInitials(initials) => {
+                initials.submit(app);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use super::{App, Playing, Run, State, Title, INPUT_DELAY};
+use crate::render::{Align, FontSize, GREY, WHITE};
+use blasteroids::{asteroid::AsteroidSize, world::World};
+use sdl2::{event::Event, keyboard::Keycode};
+use std::time::{Duration, Instant};
+
+pub struct GameOver {
+    world: World,
+    rank: Option<usize>, // place on the high score table
+    opened: Instant,
+}
+
+// 2:05 style
+fn format_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+impl GameOver {
+    pub fn new(world: World, rank: Option<usize>) -> Self {
+        GameOver {
+            world,
+            rank,
+            opened: Instant::now(),
+        }
+    }
+
+    fn accepts_input(&self) -> bool {
+        self.opened.elapsed() >= INPUT_DELAY
+    }
+
+    pub fn handle_event(self, event: &Event, app: &mut App) -> Result<State, String> {
+        match event {
+            Event::KeyDown {
+                keycode: Some(code),
+                repeat: false,
+                ..
+            } if self.accepts_input() => match code {
+                // a replay is over once it has been watched
+                _ if app.options.replay.is_some() => Ok(State::Quit),
+                Keycode::Escape => Ok(State::Title(Title::new())),
+                _ => Ok(State::Playing(Playing::new(Run::new(app)))),
+            },
+            _ => Ok(State::GameOver(self)),
+        }
+    }
+
+    // the summary as label / value rows
+    fn stats(&self) -> Vec<(&'static str, String)> {
+        let stats = &self.world.stats;
+        let mut rows = vec![
+            (
+                "large asteroids",
+                stats.destroyed(AsteroidSize::Large).to_string(),
+            ),
+            (
+                "medium asteroids",
+                stats.destroyed(AsteroidSize::Medium).to_string(),
+            ),
+            (
+                "small asteroids",
+                stats.destroyed(AsteroidSize::Small).to_string(),
+            ),
+            ("saucers", stats.saucers_destroyed.to_string()),
+            ("shots fired", stats.shots_fired.to_string()),
+            ("accuracy", format!("{:.0}%", stats.accuracy() * 100.0)),
+            ("time", format_time(self.world.elapsed())),
+        ];
+        // endless mode has no waves to count
+        if self.world.wave() > 0 {
+            rows.insert(0, ("wave", self.world.wave().to_string()));
+        }
+        rows
+    }
+
+    pub fn draw(&self, app: &mut App) -> Result<(), String> {
+        let renderer = &mut app.renderer;
+        renderer.world(&self.world, 1.0)?;
+        renderer.dim()?;
+
+        let (w, h) = renderer.size();
+        let center = w as i32 / 2;
+        let mut y = h as i32 / 6;
+        y += renderer.text(
+            "GAME OVER",
+            FontSize::Large,
+            (center, y),
+            Align::Center,
+            WHITE,
+        )? as i32;
+        y += renderer.text(
+            &format!("score {}", self.world.player.score),
+            FontSize::Medium,
+            (center, y),
+            Align::Center,
+            WHITE,
+        )? as i32;
+        if let Some(rank) = self.rank {
+            y += renderer.text(
+                &format!("new high score, #{}", rank + 1),
+                FontSize::Small,
+                (center, y),
+                Align::Center,
+                WHITE,
+            )? as i32;
+        }
+        y += 30;
+
+        // labels right aligned against the values
+        let gap = 16;
+        for (label, value) in self.stats() {
+            renderer.text(
+                label,
+                FontSize::Small,
+                (center - gap, y),
+                Align::Right,
+                GREY,
+            )?;
+            y += renderer.text(
+                &value,
+                FontSize::Small,
+                (center + gap, y),
+                Align::Left,
+                WHITE,
+            )? as i32;
+        }
+        y += 30;
+
+        if self.accepts_input() {
+            let prompt = if app.options.replay.is_some() {
+                "press any key to quit"
+            } else {
+                "any key to play again, esc for title"
+            };
+            renderer.text(prompt, FontSize::Small, (center, y), Align::Center, GREY)?;
+        }
+        Ok(())
+    }
+}
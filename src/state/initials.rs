@@ -0,0 +1,151 @@
+use super::{App, GameOver, State, INPUT_DELAY};
+use crate::render::{Align, FontSize, GREY, WHITE};
+use blasteroids::{
+    highscores::{today, HighScore, INITIALS_LEN},
+    world::World,
+};
+use sdl2::{event::Event, keyboard::Keycode};
+use std::time::Instant;
+
+/// Arcade style name entry: left / right pick a letter, space locks it in,
+/// or just type them.
+pub struct EnterInitials {
+    world: World,
+    letters: [u8; INITIALS_LEN],
+    cursor: usize,
+    opened: Instant,
+}
+
+impl EnterInitials {
+    pub fn new(world: World, app: &App) -> Self {
+        app.renderer
+            .canvas
+            .window()
+            .subsystem()
+            .text_input()
+            .start();
+        EnterInitials {
+            world,
+            letters: [b'A'; INITIALS_LEN],
+            cursor: 0,
+            opened: Instant::now(),
+        }
+    }
+
+    // step through A-Z, wrapping around
+    fn cycle(&mut self, step: i8) {
+        let letter = &mut self.letters[self.cursor];
+        *letter = b'A' + (*letter - b'A' + (26 + step) as u8) % 26;
+    }
+
+    fn set_letter(&mut self, letter: u8) {
+        self.letters[self.cursor] = letter;
+        self.cursor += 1;
+    }
+
+    fn done(&self) -> bool {
+        self.cursor >= INITIALS_LEN
+    }
+
+    /// Put the score on the table with whatever has been entered so far.
+    pub fn submit(self, app: &mut App) -> GameOver {
+        app.renderer.canvas.window().subsystem().text_input().stop();
+        let initials = String::from_utf8_lossy(&self.letters).into_owned();
+        let rank = app.submit_score(HighScore {
+            initials,
+            score: self.world.player.score,
+            date: today(),
+        });
+        GameOver::new(self.world, rank)
+    }
+
+    pub fn handle_event(mut self, event: &Event, app: &mut App) -> Result<State, String> {
+        // fire is probably still being hammered, don't sign with it
+        if self.opened.elapsed() < INPUT_DELAY {
+            return Ok(State::EnterInitials(self));
+        }
+        match event {
+            // holding an arrow scrolls through the letters
+            Event::KeyDown {
+                keycode: Some(code),
+                repeat,
+                ..
+            } => match code {
+                Keycode::Left | Keycode::Down => self.cycle(-1),
+                Keycode::Right | Keycode::Up => self.cycle(1),
+                Keycode::Space if !repeat => self.cursor += 1,
+                Keycode::Backspace => self.cursor = self.cursor.saturating_sub(1),
+                Keycode::Return if !repeat => self.cursor = INITIALS_LEN,
+                _ => {}
+            },
+            Event::TextInput { text, .. } => {
+                for c in text.chars().filter(char::is_ascii_alphabetic) {
+                    if !self.done() {
+                        self.set_letter(c.to_ascii_uppercase() as u8);
+                    }
+                }
+            }
+            _ => {}
+        }
+        if self.done() {
+            return Ok(State::GameOver(self.submit(app)));
+        }
+        Ok(State::EnterInitials(self))
+    }
+
+    pub fn draw(&self, app: &mut App) -> Result<(), String> {
+        let renderer = &mut app.renderer;
+        renderer.world(&self.world, 1.0)?;
+        renderer.dim()?;
+
+        let (w, h) = renderer.size();
+        let center = w as i32 / 2;
+        let mut y = h as i32 / 5;
+        y += renderer.text(
+            "NEW HIGH SCORE",
+            FontSize::Large,
+            (center, y),
+            Align::Center,
+            WHITE,
+        )? as i32;
+        y += renderer.text(
+            &self.world.player.score.to_string(),
+            FontSize::Medium,
+            (center, y),
+            Align::Center,
+            WHITE,
+        )? as i32;
+        y += 40;
+
+        // one slot per letter, the one being edited blinks
+        let spacing = 80;
+        let left = center - spacing * (INITIALS_LEN as i32 - 1) / 2;
+        let blink_on = self.opened.elapsed().as_millis() % 500 < 300;
+        for (i, &letter) in self.letters.iter().enumerate() {
+            let x = left + spacing * i as i32;
+            let (letter, color) = match i.cmp(&self.cursor) {
+                std::cmp::Ordering::Less => (letter as char, WHITE),
+                std::cmp::Ordering::Equal if blink_on => (letter as char, WHITE),
+                std::cmp::Ordering::Equal => ('_', WHITE),
+                std::cmp::Ordering::Greater => ('_', GREY),
+            };
+            renderer.text(
+                &letter.to_string(),
+                FontSize::Large,
+                (x, y),
+                Align::Center,
+                color,
+            )?;
+        }
+        y += 150;
+
+        renderer.text(
+            "left right to pick, space to confirm, or type",
+            FontSize::Small,
+            (center, y),
+            Align::Center,
+            GREY,
+        )?;
+        Ok(())
+    }
+}
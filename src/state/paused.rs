@@ -0,0 +1,73 @@
+use super::{App, Playing, Run, State, Title};
+use crate::render::{Align, FontSize, GREY, WHITE};
+use blasteroids::world::InputState;
+use sdl2::{
+    event::{Event, WindowEvent},
+    keyboard::Keycode,
+};
+
+pub struct Paused {
+    pub(super) run: Run,
+}
+
+impl Paused {
+    pub fn new(mut run: Run) -> Self {
+        // key ups are not seen while paused, don't keep flying on resume
+        run.input = InputState::default();
+        Paused { run }
+    }
+
+    pub fn handle_event(mut self, event: &Event, app: &mut App) -> Result<State, String> {
+        match event {
+            Event::Window {
+                win_event: WindowEvent::Resized(w, h),
+                ..
+            } => self.run.resize((*w as u32, *h as u32)),
+            Event::KeyDown {
+                keycode: Some(code),
+                repeat: false,
+                ..
+            } => match code {
+                Keycode::P | Keycode::Return | Keycode::Space => {
+                    return Ok(State::Playing(Playing::new(self.run)));
+                }
+                // give up on this run, a replay has no title screen to go back to
+                Keycode::Escape if self.run.is_replay() => return Ok(State::Quit),
+                Keycode::Escape => {
+                    self.run.finish(app)?;
+                    return Ok(State::Title(Title::new()));
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        Ok(State::Paused(self))
+    }
+
+    pub fn draw(&self, app: &mut App) -> Result<(), String> {
+        let renderer = &mut app.renderer;
+        renderer.world(&self.run.world, 1.0)?;
+        renderer.hud(&self.run.world)?;
+        renderer.dim()?;
+
+        let (w, h) = renderer.size();
+        let center = w as i32 / 2;
+        let mut y = h as i32 / 3;
+        y += renderer.text("PAUSED", FontSize::Large, (center, y), Align::Center, WHITE)? as i32;
+        y += 40;
+        y += renderer.text(
+            "p to resume",
+            FontSize::Small,
+            (center, y),
+            Align::Center,
+            GREY,
+        )? as i32;
+        let quit = if self.run.is_replay() {
+            "esc to quit"
+        } else {
+            "esc to quit to title"
+        };
+        renderer.text(quit, FontSize::Small, (center, y), Align::Center, GREY)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use super::{load_config, App, EnterInitials, GameOver, Paused, Run, State};
+use sdl2::{
+    event::{Event, WindowEvent},
+    keyboard::Keycode,
+};
+
+pub struct Playing {
+    pub(super) run: Run,
+}
+
+impl Playing {
+    pub fn new(run: Run) -> Self {
+        Playing { run }
+    }
+
+    pub fn handle_event(mut self, event: &Event, app: &mut App) -> Result<State, String> {
+        let input = &mut self.run.input;
+        match event {
+            // update viewport on resize
+            Event::Window {
+                win_event: WindowEvent::Resized(w, h),
+                ..
+            } => self.run.resize((*w as u32, *h as u32)),
+
+            // player controls
+            Event::KeyDown {
+                keycode: Some(code),
+                repeat: false,
+                ..
+            } => match code {
+                Keycode::Up => input.thrust = true,
+                Keycode::Left => input.turn_left = true,
+                Keycode::Right => input.turn_right = true,
+                Keycode::Space => {
+                    input.fire = true;
+                    input.fire_held = true;
+                }
+                Keycode::Down => input.hyperspace = true,
+                Keycode::LShift | Keycode::RShift => input.shield = true,
+                Keycode::Tab => self.run.switch_weapon(),
+                Keycode::Escape | Keycode::P => return Ok(State::Paused(Paused::new(self.run))),
+                // live tuning, would break exact playback
+                Keycode::F5 if self.run.is_recording() || self.run.is_replay() => {
+                    println!("config reload is disabled while recording or replaying");
+                }
+                Keycode::F5 => match load_config(&app.options.config) {
+                    Ok(config) => {
+                        self.run.world.set_config(config.clone());
+                        app.config = config;
+                        println!("reloaded {}", app.options.config.display());
+                    }
+                    Err(err) => eprintln!("{}", err),
+                },
+                _ => {}
+            },
+            Event::KeyUp {
+                keycode: Some(code),
+                ..
+            } => match code {
+                Keycode::Up => input.thrust = false,
+                Keycode::Left => input.turn_left = false,
+                Keycode::Right => input.turn_right = false,
+                Keycode::Space => input.fire_held = false,
+                Keycode::LShift | Keycode::RShift => input.shield = false,
+                _ => {}
+            },
+            _ => {}
+        }
+        Ok(State::Playing(self))
+    }
+
+    pub fn tick(mut self, app: &mut App) -> Result<State, String> {
+        if !self.run.step(app)? {
+            return Ok(State::Quit);
+        }
+        if self.run.world.is_game_over() {
+            // watching a replay doesn't earn a high score
+            let replay = self.run.is_replay();
+            let world = self.run.finish(app)?;
+            if !replay && app.highscores.qualifies(world.player.score) {
+                return Ok(State::EnterInitials(EnterInitials::new(world, app)));
+            }
+            return Ok(State::GameOver(GameOver::new(world, None)));
+        }
+        Ok(State::Playing(self))
+    }
+
+    pub fn draw(&self, app: &mut App, alpha: f32) -> Result<(), String> {
+        app.renderer.world(&self.run.world, alpha)?;
+        app.renderer.hud(&self.run.world)
+    }
+}
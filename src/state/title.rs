@@ -0,0 +1,94 @@
+use super::{App, Playing, Run, State};
+use crate::render::{Align, FontSize, GREY, WHITE};
+use sdl2::{event::Event, keyboard::Keycode};
+use std::time::Instant;
+
+pub struct Title {
+    opened: Instant,
+}
+
+impl Title {
+    pub fn new() -> Self {
+        Title {
+            opened: Instant::now(),
+        }
+    }
+
+    pub fn handle_event(self, event: &Event, app: &mut App) -> Result<State, String> {
+        match event {
+            Event::KeyDown {
+                keycode: Some(Keycode::Space | Keycode::Return),
+                repeat: false,
+                ..
+            } => Ok(State::Playing(Playing::new(Run::new(app)))),
+            Event::KeyDown {
+                keycode: Some(Keycode::Escape),
+                repeat: false,
+                ..
+            } => Ok(State::Quit),
+            _ => Ok(State::Title(self)),
+        }
+    }
+
+    pub fn draw(&self, app: &mut App) -> Result<(), String> {
+        let renderer = &mut app.renderer;
+        let (w, h) = renderer.size();
+        let center = w as i32 / 2;
+        let mut y = h as i32 / 10;
+
+        y += renderer.text(
+            "BLASTEROIDS",
+            FontSize::Large,
+            (center, y),
+            Align::Center,
+            WHITE,
+        )? as i32;
+        // blink the prompt, once a second
+        if self.opened.elapsed().as_millis() % 1000 < 600 {
+            renderer.text(
+                "press space to start",
+                FontSize::Medium,
+                (center, y),
+                Align::Center,
+                WHITE,
+            )?;
+        }
+        y += 100;
+
+        // high score table: rank, initials, score, date
+        let scores = app.highscores.entries();
+        if !scores.is_empty() {
+            y += renderer.text(
+                "high scores",
+                FontSize::Small,
+                (center, y),
+                Align::Center,
+                GREY,
+            )? as i32;
+            y += 10;
+        }
+        for (rank, entry) in scores.iter().enumerate() {
+            let row = [
+                (format!("{}.", rank + 1), center - 170, Align::Right, GREY),
+                (entry.initials.clone(), center - 150, Align::Left, WHITE),
+                (entry.score.to_string(), center + 70, Align::Right, WHITE),
+                (entry.date.clone(), center + 100, Align::Left, GREY),
+            ];
+            let mut height = 0;
+            for (text, x, align, color) in row {
+                height = renderer.text(&text, FontSize::Small, (x, y), align, color)?;
+            }
+            y += height as i32;
+        }
+
+        let controls = [
+            "arrows fly   space fire   tab weapon   shift shield",
+            "down hyperspace   p pause   esc quit",
+        ];
+        let mut y = h as i32 - 90;
+        for line in controls {
+            y += renderer.text(line, FontSize::Small, (center, y), Align::Center, GREY)? as i32;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How the ship fires. Every weapon shoots the same projectile, they differ in
+/// how often, how many at once, how fast and how hard.
+pub trait Weapon {
+    /// Shortest time from one volley to the next.
+    fn cooldown(&self) -> Duration;
+    /// Projectiles per volley.
+    fn projectile_count(&self) -> usize;
+    /// Radians between neighbouring projectiles of a volley.
+    fn spread(&self) -> f32;
+    /// Times `projectile.speed`.
+    fn speed(&self) -> f32;
+    /// Asteroids one projectile breaks before it is spent.
+    fn damage(&self) -> u32;
+
+    /// Keeps firing while the button is held instead of once per press.
+    fn automatic(&self) -> bool {
+        false
+    }
+
+    /// Headings of one volley aimed at `angle`, fanned out evenly around it.
+    fn volley(&self, angle: f32) -> Vec<f32> {
+        let count = self.projectile_count();
+        let middle = (count as f32 - 1.0) / 2.0;
+        (0..count)
+            .map(|i| angle + (i as f32 - middle) * self.spread())
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WeaponKind {
+    Single,
+    Spread,
+    Rapid,
+    Piercing,
+}
+
+impl WeaponKind {
+    /// In the order the weapon key cycles through them.
+    pub const ALL: [WeaponKind; 4] = [
+        WeaponKind::Single,
+        WeaponKind::Piercing,
+        WeaponKind::Spread,
+        WeaponKind::Rapid,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WeaponKind::Single => "single",
+            WeaponKind::Spread => "spread",
+            WeaponKind::Rapid => "rapid",
+            WeaponKind::Piercing => "piercing",
+        }
+    }
+}
+
+/// One shot straight ahead per press.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SingleShot {
+    pub cooldown: f32, // seconds
+}
+
+/// Three shots fanned out per press.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TripleSpread {
+    pub cooldown: f32,
+    pub spread: f32, // radians between the shots
+}
+
+/// Keeps shooting while fire is held.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RapidBurst {
+    pub interval: f32, // seconds between shots
+}
+
+/// A slower firing, faster shot that goes through asteroids.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PiercingShot {
+    pub cooldown: f32,
+    pub speed: f32,  // times projectile.speed
+    pub damage: u32, // asteroids broken before it's spent
+}
+
+impl Default for SingleShot {
+    fn default() -> Self {
+        SingleShot { cooldown: 0.0 }
+    }
+}
+
+impl Default for TripleSpread {
+    fn default() -> Self {
+        TripleSpread {
+            cooldown: 0.0,
+            spread: 0.2,
+        }
+    }
+}
+
+impl Default for RapidBurst {
+    fn default() -> Self {
+        RapidBurst { interval: 0.12 }
+    }
+}
+
+impl Default for PiercingShot {
+    fn default() -> Self {
+        PiercingShot {
+            cooldown: 0.4,
+            speed: 1.5,
+            damage: 3,
+        }
+    }
+}
+
+impl Weapon for SingleShot {
+    fn cooldown(&self) -> Duration {
+        Duration::from_secs_f32(self.cooldown)
+    }
+
+    fn projectile_count(&self) -> usize {
+        1
+    }
+
+    fn spread(&self) -> f32 {
+        0.0
+    }
+
+    fn speed(&self) -> f32 {
+        1.0
+    }
+
+    fn damage(&self) -> u32 {
+        1
+    }
+}
+
+impl Weapon for TripleSpread {
+    fn cooldown(&self) -> Duration {
+        Duration::from_secs_f32(self.cooldown)
+    }
+
+    fn projectile_count(&self) -> usize {
+        3
+    }
+
+    fn spread(&self) -> f32 {
+        self.spread
+    }
+
+    fn speed(&self) -> f32 {
+        1.0
+    }
+
+    fn damage(&self) -> u32 {
+        1
+    }
+}
+
+impl Weapon for RapidBurst {
+    fn cooldown(&self) -> Duration {
+        Duration::from_secs_f32(self.interval)
+    }
+
+    fn projectile_count(&self) -> usize {
+        1
+    }
+
+    fn spread(&self) -> f32 {
+        0.0
+    }
+
+    fn speed(&self) -> f32 {
+        1.0
+    }
+
+    fn damage(&self) -> u32 {
+        1
+    }
+
+    fn automatic(&self) -> bool {
+        true
+    }
+}
+
+impl Weapon for PiercingShot {
+    fn cooldown(&self) -> Duration {
+        Duration::from_secs_f32(self.cooldown)
+    }
+
+    fn projectile_count(&self) -> usize {
+        1
+    }
+
+    fn spread(&self) -> f32 {
+        0.0
+    }
+
+    fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    fn damage(&self) -> u32 {
+        self.damage
+    }
+}
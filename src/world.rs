@@ -0,0 +1,1447 @@
+use crate::aim::{aim_angle, aim_error, lead_target};
+use crate::asteroid::{
+    deflect_asteroid, pick_random_velocity, pick_spawn_point, resolve_bounce, spawn_asteroid,
+    split_asteroid, Asteroid, AsteroidSize, ShapeSource,
+};
+use crate::config::GameConfig;
+use crate::geometry::{
+    adjust_pos_for_resize, check_collision, heading, normalize_angle, outline_radius,
+    outlines_intersect, rotate, translate, wrap_position, Vec2,
+};
+use crate::powerup::{pick_powerup_kind, spawn_powerup, PowerUp, PowerUpKind};
+use crate::saucer::{pick_saucer_size, spawn_saucer, Saucer, SaucerSize};
+use crate::shapes::Shapes;
+use crate::spatial::SpatialHash;
+use crate::weapon::WeaponKind;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{f32::consts::TAU, time::Duration};
+
+/// Simulation rate, independent of how fast frames are rendered.
+pub const TICK_RATE: u64 = 120;
+pub const TICK: Duration = Duration::from_nanos(1_000_000_000 / TICK_RATE);
+
+// gameplay tuning lives in `GameConfig`
+const PLAYER_MARGIN: f32 = 0.0;
+const GRID_CELL_SIZE: f32 = 64.0; // about a large asteroid across
+const BOUNCE_PUSH_SPEED: f32 = 120.0; // how fast overlapping asteroids are pulled apart
+const PROJECTILE_RADIUS: f32 = 3.0;
+
+/// Player controls for a single step, filled in by whatever drives the world.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct InputState {
+    pub thrust: bool,
+    pub turn_left: bool,
+    pub turn_right: bool,
+    /// Fire was pressed since the last step (edge, not held).
+    pub fire: bool,
+    /// Fire is being held down, only automatic weapons care.
+    pub fire_held: bool,
+    /// Hyperspace was pressed since the last step (edge, not held).
+    pub hyperspace: bool,
+    pub shield: bool,
+}
+
+/// Optional rules, fixed for a whole run so replays stay exact.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct GameMode {
+    /// After the circle test passes, check the actual ship / asteroid / projectile outlines.
+    pub polygon_collisions: bool,
+    /// Asteroids bounce off each other instead of passing through.
+    pub asteroid_bounces: bool,
+    /// Every asteroid gets a freshly generated outline instead of one from the shapes file.
+    pub procedural_asteroids: bool,
+    /// Keep the field topped up forever instead of playing in waves.
+    pub endless: bool,
+}
+
+/// Things that happened during a step the frontend may want to react to (e.g. sfx).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WorldEvent {
+    Shot,
+    AsteroidDestroyed,
+    PlayerHit,
+    ExtraLife,
+    Hyperspace,
+    ShieldHit,
+    SaucerShot,
+    SaucerDestroyed,
+    PowerUp,
+}
+
+/// Running totals for the end of game summary.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct RunStats {
+    pub shots_fired: u32,
+    pub shots_hit: u32,
+    pub large_destroyed: u32,
+    pub medium_destroyed: u32,
+    pub small_destroyed: u32,
+    pub saucers_destroyed: u32,
+}
+
+impl RunStats {
+    pub fn destroyed(&self, size: AsteroidSize) -> u32 {
+        match size {
+            AsteroidSize::Large => self.large_destroyed,
+            AsteroidSize::Medium => self.medium_destroyed,
+            AsteroidSize::Small => self.small_destroyed,
+        }
+    }
+
+    /// Fraction of shots that hit something, 0 when nothing was fired.
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            0.0
+        } else {
+            self.shots_hit as f32 / self.shots_fired as f32
+        }
+    }
+
+    fn count_destroyed(&mut self, size: AsteroidSize) {
+        match size {
+            AsteroidSize::Large => self.large_destroyed += 1,
+            AsteroidSize::Medium => self.medium_destroyed += 1,
+            AsteroidSize::Small => self.small_destroyed += 1,
+        }
+    }
+}
+
+/// A hyperspace jump in progress, for drawing.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Jump {
+    pub from: Vec2,
+    pub to: Vec2,
+    /// 0 when the ship starts to vanish, 1 when it is fully back.
+    pub progress: f32,
+}
+
+pub struct Projectile {
+    pub pos: Vec2,
+    pub vel: Vec2,
+    pub angle: f32,
+    pub radius: f32,
+    pub prev_pos: Vec2,
+    pub damage: u32, // spent after hitting this many things
+    pub hits: u32,
+    pierced: Vec<u32>, // ids of what it went through and the fragments left in its way
+}
+
+pub struct Player {
+    pub pos: Vec2,
+    pub vel: Vec2,
+    pub angle: f32,
+    pub prev_pos: Vec2,
+    pub prev_angle: f32,
+    pub health: i32,
+    pub score: i32,
+    pub thrusting: bool,
+    pub shielding: bool,
+    pub shield_energy: f32, // 0 to 1
+    last_hit: Option<Duration>,
+    died_at: Option<Duration>,      // exploded and waiting to respawn
+    jump: Option<(Vec2, Duration)>, // hyperspace origin and start time
+    last_jump: Option<Duration>,
+}
+
+impl Player {
+    fn new(viewport: (u32, u32), health: i32) -> Self {
+        let pos = (viewport.0 as f32 / 2.0, viewport.1 as f32 / 2.0);
+        Player {
+            pos,
+            vel: (0.0, 0.0),
+            angle: 0.0,
+            prev_pos: pos,
+            prev_angle: 0.0,
+            health,
+            score: 0,
+            thrusting: false,
+            shielding: false,
+            shield_energy: 1.0,
+            last_hit: None,
+            died_at: None,
+            jump: None,
+            last_jump: None,
+        }
+    }
+
+    /// False between exploding and respawning.
+    pub fn is_alive(&self) -> bool {
+        self.died_at.is_none()
+    }
+
+    // alive and not in hyperspace, the only time it steers, shoots or collides
+    fn is_flying(&self) -> bool {
+        self.is_alive() && self.jump.is_none()
+    }
+}
+
+// broad phase radius and, with polygon collisions, the segment for the narrow phase
+fn projectile_hitbox(
+    projectile: &Projectile,
+    shape: &[Vec2],
+    polygon: bool,
+) -> (f32, Option<Vec<Vec2>>) {
+    if polygon {
+        let segment = translate(&rotate(shape, projectile.angle), projectile.pos);
+        (projectile.radius.max(outline_radius(shape)), Some(segment))
+    } else {
+        (projectile.radius, None)
+    }
+}
+
+// free function so the rest of the world stays borrowable
+fn shape_source<'a>(
+    mode: &GameMode,
+    shapes: &'a Shapes,
+    config: &'a GameConfig,
+) -> ShapeSource<'a> {
+    if mode.procedural_asteroids {
+        ShapeSource::Generated(&config.asteroids.procedural)
+    } else {
+        ShapeSource::Outlines(&shapes.asteroids)
+    }
+}
+
+/// The whole game simulation, independent of SDL so it can run headless.
+pub struct World {
+    pub viewport: (u32, u32),
+    pub mode: GameMode,
+    pub player: Player,
+    pub asteroids: Vec<Asteroid>,
+    pub projectiles: Vec<Projectile>,
+    pub saucer: Option<Saucer>,
+    pub saucer_projectiles: Vec<Projectile>,
+    pub powerups: Vec<PowerUp>,
+    pub stats: RunStats,
+    time: Duration,
+    seed: u64,
+    rng: StdRng,
+    events: Vec<WorldEvent>,
+    grid: SpatialHash,      // asteroids, rebuilt every tick
+    candidates: Vec<usize>, // reused grid query buffer
+    shapes: Shapes,
+    config: GameConfig,
+    wave: u32, // 0 until the first wave spawns, stays 0 in endless mode
+    next_wave_at: Option<Duration>, // set once a wave is cleared
+    next_extra_life: Option<i32>, // score that awards the next life
+    last_extra_life: Option<Duration>,
+    next_saucer_at: Duration,              // once the current saucer is gone
+    effects: Vec<(PowerUpKind, Duration)>, // timed power-ups and when they run out
+    weapon: WeaponKind,
+    last_shot: Option<Duration>,
+    next_id: u32, // for asteroids and saucers
+}
+
+impl World {
+    /// Same seed and same inputs give the same game.
+    pub fn new(
+        viewport: (u32, u32),
+        seed: u64,
+        mode: GameMode,
+        shapes: Shapes,
+        config: GameConfig,
+    ) -> Self {
+        let mut world = World {
+            viewport,
+            mode,
+            player: Player::new(viewport, config.lives.starting),
+            asteroids: Vec::new(),
+            projectiles: Vec::new(),
+            saucer: None,
+            saucer_projectiles: Vec::new(),
+            powerups: Vec::new(),
+            stats: RunStats::default(),
+            time: Duration::ZERO,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            events: Vec::new(),
+            grid: SpatialHash::new(GRID_CELL_SIZE),
+            candidates: Vec::new(),
+            shapes,
+            next_extra_life: config.lives.first_threshold(),
+            next_saucer_at: config.saucers.interval(),
+            config,
+            wave: 0,
+            next_wave_at: None,
+            last_extra_life: None,
+            effects: Vec::new(),
+            weapon: WeaponKind::Single,
+            last_shot: None,
+            next_id: 0,
+        };
+        if world.mode.endless {
+            world.fill_asteroids();
+        } else {
+            // the first wave gets announced like every other
+            world.next_wave_at = Some(world.config.waves.delay());
+        }
+        world
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn shapes(&self) -> &Shapes {
+        &self.shapes
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Swap tuning values mid game, takes effect on the next step.
+    pub fn set_config(&mut self, config: GameConfig) {
+        self.config = config;
+    }
+
+    pub fn resize(&mut self, viewport: (u32, u32)) {
+        let old_viewport = self.viewport;
+        self.viewport = viewport;
+        adjust_pos_for_resize(&mut self.player.pos, old_viewport, viewport);
+    }
+
+    /// Simulated time since the run started, stops at game over.
+    pub fn elapsed(&self) -> Duration {
+        self.time
+    }
+
+    /// Current wave, 0 before the first one and in endless mode.
+    pub fn wave(&self) -> u32 {
+        self.wave
+    }
+
+    /// The wave about to spawn while the field is empty between waves.
+    pub fn upcoming_wave(&self) -> Option<u32> {
+        self.next_wave_at.map(|_| self.wave + 1)
+    }
+
+    /// The ship is out of lives and done exploding, `step` does nothing from here on.
+    pub fn is_game_over(&self) -> bool {
+        self.player.health <= 0
+            && self
+                .since_death()
+                .is_none_or(|since| since >= self.config.ship.respawn_delay())
+    }
+
+    /// Time since the ship exploded, `None` while it is flying.
+    pub fn since_death(&self) -> Option<Duration> {
+        Some(self.time - self.player.died_at?)
+    }
+
+    /// The ship could respawn but an asteroid is sitting in the safe zone.
+    pub fn respawn_blocked(&self) -> bool {
+        self.player.health > 0
+            && self
+                .since_death()
+                .is_some_and(|since| since >= self.config.ship.respawn_delay())
+            && !self.respawn_zone_clear()
+    }
+
+    /// Time since the last hit while the ship is still invulnerable.
+    pub fn invulnerable_elapsed(&self) -> Option<Duration> {
+        let elapsed = self.time - self.player.last_hit?;
+        (elapsed < self.config.ship.iframe_duration()).then_some(elapsed)
+    }
+
+    /// Time since the last extra life was awarded.
+    pub fn since_extra_life(&self) -> Option<Duration> {
+        Some(self.time - self.last_extra_life?)
+    }
+
+    /// The hyperspace jump in progress, if any.
+    pub fn jump(&self) -> Option<Jump> {
+        let (from, started) = self.player.jump?;
+        let duration = self.config.hyperspace.duration();
+        let progress = if duration.is_zero() {
+            1.0
+        } else {
+            (self.time - started).as_secs_f32() / duration.as_secs_f32()
+        };
+        Some(Jump {
+            from,
+            to: self.player.pos,
+            progress: progress.min(1.0),
+        })
+    }
+
+    /// Hyperspace can be used again.
+    pub fn jump_ready(&self) -> bool {
+        self.player.is_flying()
+            && self
+                .player
+                .last_jump
+                .is_none_or(|at| self.time - at >= self.config.hyperspace.cooldown())
+    }
+
+    /// Timed power-ups in effect and how long each one has left.
+    pub fn effects(&self) -> impl Iterator<Item = (PowerUpKind, Duration)> + '_ {
+        self.effects
+            .iter()
+            .map(|&(kind, until)| (kind, until.saturating_sub(self.time)))
+    }
+
+    pub fn weapon(&self) -> WeaponKind {
+        self.weapon
+    }
+
+    /// Single and piercing are always there, spread and rapid only while
+    /// their power-up lasts.
+    pub fn weapon_available(&self, kind: WeaponKind) -> bool {
+        match kind {
+            WeaponKind::Single | WeaponKind::Piercing => true,
+            WeaponKind::Spread => self.has_effect(PowerUpKind::Spread),
+            WeaponKind::Rapid => self.has_effect(PowerUpKind::RapidFire),
+        }
+    }
+
+    /// Move on to the next available weapon.
+    pub fn switch_weapon(&mut self) {
+        let all = WeaponKind::ALL;
+        let current = all
+            .iter()
+            .position(|&kind| kind == self.weapon)
+            .unwrap_or(0);
+        self.weapon = (1..all.len())
+            .map(|offset| all[(current + offset) % all.len()])
+            .find(|&kind| self.weapon_available(kind))
+            .unwrap_or(self.weapon);
+    }
+
+    /// Time since a power-up was dropped, it vanishes at `powerups.lifetime`.
+    pub fn powerup_age(&self, powerup: &PowerUp) -> Duration {
+        self.time - powerup.dropped
+    }
+
+    /// Events produced since the last call.
+    pub fn drain_events(&mut self) -> std::vec::Drain<'_, WorldEvent> {
+        self.events.drain(..)
+    }
+
+    /// Advance the simulation by one fixed `TICK`.
+    pub fn step(&mut self, input: &InputState) {
+        if self.is_game_over() {
+            return;
+        }
+        let dt = TICK.as_secs_f32();
+        self.time += TICK;
+        self.store_prev();
+
+        if input.hyperspace && self.jump_ready() {
+            self.start_jump();
+        }
+        self.update_jump();
+
+        // a power-up weapon that ran out goes back to the plain one
+        self.effects.retain(|&(_, until)| until > self.time);
+        if !self.weapon_available(self.weapon) {
+            self.weapon = WeaponKind::Single;
+        }
+
+        let weapon = self.config.weapons.get(self.weapon);
+        let trigger = input.fire || (input.fire_held && weapon.automatic());
+        let ready = self
+            .last_shot
+            .is_none_or(|at| self.time - at >= weapon.cooldown());
+        if trigger && ready && self.player.is_flying() {
+            self.fire();
+        }
+
+        // update projectiles
+        for p in self
+            .projectiles
+            .iter_mut()
+            .chain(&mut self.saucer_projectiles)
+        {
+            p.pos.0 += p.vel.0 * dt;
+            p.pos.1 += p.vel.1 * dt;
+        }
+
+        // update asteroids
+        let asteroid_config = &self.config.asteroids;
+        for asteroid in self.asteroids.iter_mut() {
+            asteroid.pos.0 += asteroid.vel.0 * dt;
+            asteroid.pos.1 += asteroid.vel.1 * dt;
+            asteroid.angle = (asteroid.angle + asteroid_config.spin * dt).rem_euclid(TAU);
+            wrap_position(&mut asteroid.pos, self.viewport, asteroid_config.margin);
+        }
+        self.move_saucer(dt);
+        self.move_powerups(dt);
+
+        if self.player.is_flying() {
+            self.move_player(input, dt);
+        } else {
+            self.player.thrusting = false;
+            self.try_respawn();
+        }
+        self.update_shield(input, dt);
+
+        self.rebuild_grid();
+        if self.mode.asteroid_bounces {
+            self.bounce_asteroids(dt);
+            self.rebuild_grid();
+        }
+
+        if self.player.shielding {
+            self.deflect_asteroids();
+        }
+
+        // Check player collision
+        if self.player.is_flying()
+            && !self.player.shielding
+            && self.invulnerable_elapsed().is_none()
+        {
+            let player = &self.player;
+            let ship_radius = self.ship_radius();
+            let ship = self
+                .mode
+                .polygon_collisions
+                .then(|| translate(&rotate(&self.shapes.ship, player.angle), player.pos));
+            self.grid
+                .query(player.pos, ship_radius, &mut self.candidates);
+            let hit = self.candidates.iter().any(|&ai| {
+                let asteroid = &self.asteroids[ai];
+                check_collision(player.pos, ship_radius, asteroid.pos, asteroid.radius)
+                    && ship
+                        .as_ref()
+                        .is_none_or(|ship| outlines_intersect(ship, &asteroid.world_outline()))
+            });
+            if hit {
+                self.hit_player();
+            }
+        }
+        self.saucer_attacks();
+        self.collect_powerups();
+
+        if self.is_game_over() {
+            return;
+        }
+
+        self.collide_projectiles(false);
+        self.shoot_saucer();
+        // saucers hit asteroids too, the grid is stale after the player's shots
+        if self.saucer.is_some() || !self.saucer_projectiles.is_empty() {
+            self.rebuild_grid();
+            self.collide_projectiles(true);
+            self.rebuild_grid();
+            self.collide_saucer_with_asteroids();
+        }
+        self.award_extra_lives();
+
+        if self.mode.endless {
+            // continuesly spawn asteroids
+            self.fill_asteroids();
+        } else {
+            self.update_waves();
+        }
+        self.update_saucer();
+
+        // destroy off-screen projectiles
+        let (vw, vh) = (self.viewport.0 as f32, self.viewport.1 as f32);
+        let on_screen =
+            |p: &Projectile| p.pos.0 >= 0.0 && p.pos.0 <= vw && p.pos.1 >= 0.0 && p.pos.1 <= vh;
+        self.projectiles.retain(on_screen);
+        self.saucer_projectiles.retain(on_screen);
+
+        self.player.angle = normalize_angle(self.player.angle);
+    }
+
+    fn rebuild_grid(&mut self) {
+        self.grid.rebuild(
+            self.viewport,
+            self.asteroids.iter().map(|a| (a.pos, a.radius)),
+        );
+    }
+
+    fn bounce_asteroids(&mut self, dt: f32) {
+        for i in 0..self.asteroids.len() {
+            let asteroid = &self.asteroids[i];
+            self.grid
+                .query(asteroid.pos, asteroid.radius, &mut self.candidates);
+            for &j in self.candidates.iter().filter(|&&j| j > i) {
+                let (head, tail) = self.asteroids.split_at_mut(j);
+                let (a, b) = (&mut head[i], &mut tail[0]);
+                if !check_collision(a.pos, a.radius, b.pos, b.radius) {
+                    continue;
+                }
+                if self.mode.polygon_collisions
+                    && !outlines_intersect(&a.world_outline(), &b.world_outline())
+                {
+                    continue;
+                }
+                resolve_bounce(a, b, BOUNCE_PUSH_SPEED * dt);
+            }
+        }
+    }
+
+    // broad phase radius, the outline sticks out further than the circle so
+    // `ship.radius` only counts without polygon collisions
+    fn ship_radius(&self) -> f32 {
+        if self.mode.polygon_collisions {
+            outline_radius(&self.shapes.ship)
+        } else {
+            self.config.ship.radius
+        }
+    }
+
+    // remember where everything was so rendering can interpolate
+    fn store_prev(&mut self) {
+        self.player.prev_pos = self.player.pos;
+        self.player.prev_angle = self.player.angle;
+        for asteroid in self.asteroids.iter_mut() {
+            asteroid.prev_pos = asteroid.pos;
+            asteroid.prev_angle = asteroid.angle;
+        }
+        for p in self
+            .projectiles
+            .iter_mut()
+            .chain(&mut self.saucer_projectiles)
+        {
+            p.prev_pos = p.pos;
+        }
+        if let Some(saucer) = &mut self.saucer {
+            saucer.prev_pos = saucer.pos;
+        }
+        for powerup in self.powerups.iter_mut() {
+            powerup.prev_pos = powerup.pos;
+        }
+    }
+
+    fn fire(&mut self) {
+        let player = &self.player;
+        let weapon = self.config.weapons.get(self.weapon);
+        let speed = self.config.projectile.speed * weapon.speed();
+        for angle in weapon.volley(player.angle) {
+            let dir = heading(angle);
+            self.projectiles.push(Projectile {
+                pos: player.pos,
+                vel: (speed * dir.0, speed * dir.1),
+                angle,
+                radius: PROJECTILE_RADIUS,
+                prev_pos: player.pos,
+                damage: weapon.damage(),
+                hits: 0,
+                pierced: Vec::new(),
+            });
+            self.stats.shots_fired += 1;
+        }
+        self.last_shot = Some(self.time);
+        self.events.push(WorldEvent::Shot);
+    }
+
+    fn move_player(&mut self, input: &InputState, dt: f32) {
+        let player = &mut self.player;
+        let ship = &self.config.ship;
+        player.thrusting = input.thrust;
+
+        // Movement
+        if input.thrust {
+            let dir = heading(player.angle);
+            player.vel.0 += ship.acceleration * dir.0 * dt;
+            player.vel.1 += ship.acceleration * dir.1 * dt;
+        } else {
+            let drag = ship.drag.powf(dt);
+            player.vel.0 *= drag;
+            player.vel.1 *= drag;
+        }
+
+        // Rotation
+        if input.turn_left {
+            player.angle -= ship.turn_speed * dt;
+        }
+        if input.turn_right {
+            player.angle += ship.turn_speed * dt;
+        }
+
+        // compute new position
+        player.pos.0 += player.vel.0 * dt;
+        player.pos.1 += player.vel.1 * dt;
+        wrap_position(&mut player.pos, self.viewport, PLAYER_MARGIN);
+    }
+
+    // each projectile destroys at most one asteroid, lowest index first,
+    // only the player's shots score
+    fn collide_projectiles(&mut self, from_saucer: bool) {
+        let mut dead_asteroids = vec![false; self.asteroids.len()];
+        let mut spawned_children = Vec::new();
+
+        let polygon = self.mode.polygon_collisions;
+        let speed_scale = self.speed_scale();
+        let multiplier = self.score_multiplier();
+        let shapes = shape_source(&self.mode, &self.shapes, &self.config);
+        let mut projectiles = std::mem::take(if from_saucer {
+            &mut self.saucer_projectiles
+        } else {
+            &mut self.projectiles
+        });
+        projectiles.retain_mut(|projectile| {
+            let (radius, segment) = projectile_hitbox(projectile, &self.shapes.projectile, polygon);
+            self.grid
+                .query(projectile.pos, radius, &mut self.candidates);
+            let hit = self.candidates.iter().copied().find(|&ai| {
+                let asteroid = &self.asteroids[ai];
+                !dead_asteroids[ai]
+                    && !projectile.pierced.contains(&asteroid.id)
+                    && check_collision(projectile.pos, radius, asteroid.pos, asteroid.radius)
+                    && segment.as_ref().is_none_or(|segment| {
+                        outlines_intersect(segment, &asteroid.world_outline())
+                    })
+            });
+            let Some(ai) = hit else {
+                return true;
+            };
+            let asteroid = &self.asteroids[ai];
+            if !from_saucer {
+                self.player.score += self.config.asteroids.size(asteroid.size).score * multiplier;
+                // a shot going through several asteroids only hit once for accuracy
+                if projectile.hits == 0 {
+                    self.stats.shots_hit += 1;
+                }
+                self.stats.count_destroyed(asteroid.size);
+            }
+            self.events.push(WorldEvent::AsteroidDestroyed);
+            dead_asteroids[ai] = true;
+            // the fragments start right on the shot, it goes through them too
+            projectile.pierced.push(asteroid.id);
+            let children = split_asteroid(asteroid, &mut self.rng, &shapes, &self.config.asteroids);
+            spawned_children.extend(children.into_iter().map(|mut child| {
+                child.vel.0 *= speed_scale;
+                child.vel.1 *= speed_scale;
+                child.id = self.next_id;
+                self.next_id += 1;
+                projectile.pierced.push(child.id);
+                child
+            }));
+            // only the player's shots shake power-ups loose
+            let powerups = &self.config.powerups;
+            if !from_saucer
+                && powerups.drop_chance > 0.0
+                && self.rng.gen::<f32>() < powerups.drop_chance
+            {
+                let kind = pick_powerup_kind(&mut self.rng, powerups);
+                let powerup = spawn_powerup(kind, asteroid.pos, self.time, powerups, &mut self.rng);
+                self.powerups.push(powerup);
+            }
+            projectile.hits += 1;
+            projectile.hits < projectile.damage
+        });
+        if from_saucer {
+            self.saucer_projectiles = projectiles;
+        } else {
+            self.projectiles = projectiles;
+        }
+
+        // delete when destroyed
+        let mut dead = dead_asteroids.into_iter();
+        self.asteroids.retain(|_| !dead.next().unwrap_or(false));
+
+        self.asteroids.extend(spawned_children);
+    }
+
+    // up while held and there is energy left, only recharges once released
+    // so an empty shield doesn't flicker back on
+    fn update_shield(&mut self, input: &InputState, dt: f32) {
+        let shield = &self.config.shield;
+        let player = &mut self.player;
+        player.shielding = input.shield && player.is_flying() && player.shield_energy > 0.0;
+        if player.shielding {
+            player.shield_energy -= shield.drain * dt;
+        } else if !input.shield {
+            player.shield_energy += shield.regen * dt;
+        }
+        player.shield_energy = player.shield_energy.clamp(0.0, 1.0);
+    }
+
+    fn deflect_asteroids(&mut self) {
+        let shield = &self.config.shield;
+        let player = &mut self.player;
+        self.grid
+            .query(player.pos, shield.radius, &mut self.candidates);
+        let mut deflected = false;
+        for &ai in &self.candidates {
+            if deflect_asteroid(
+                &mut self.asteroids[ai],
+                player.pos,
+                player.vel,
+                shield.radius,
+            ) {
+                player.shield_energy = (player.shield_energy - shield.hit_cost).max(0.0);
+                self.events.push(WorldEvent::ShieldHit);
+                deflected = true;
+            }
+        }
+        // asteroids were pushed out, keep the grid honest for the projectiles
+        if deflected {
+            self.rebuild_grid();
+        }
+    }
+
+    // circle (and outline) test against the ship, or just the shield while it's up
+    fn touches_ship(&self, pos: Vec2, radius: f32, outline: Option<Vec<Vec2>>) -> bool {
+        let player = &self.player;
+        if player.shielding {
+            return check_collision(pos, radius, player.pos, self.config.shield.radius);
+        }
+        check_collision(pos, radius, player.pos, self.ship_radius())
+            && outline.is_none_or(|outline| {
+                let ship = translate(&rotate(&self.shapes.ship, player.angle), player.pos);
+                outlines_intersect(&ship, &outline)
+            })
+    }
+
+    // saucer shots and the saucer itself against the ship
+    fn saucer_attacks(&mut self) {
+        if !self.player.is_flying() || self.invulnerable_elapsed().is_some() {
+            return;
+        }
+        let polygon = self.mode.polygon_collisions;
+        let shot = self.saucer_projectiles.iter().position(|p| {
+            let (radius, segment) = projectile_hitbox(p, &self.shapes.projectile, polygon);
+            self.touches_ship(p.pos, radius, segment)
+        });
+        let rammed = self.saucer.as_ref().is_some_and(|saucer| {
+            let outline = polygon.then(|| saucer.world_outline());
+            self.touches_ship(saucer.pos, saucer.radius, outline)
+        });
+        if let Some(pi) = shot {
+            self.saucer_projectiles.remove(pi);
+        }
+        // ramming it still counts as shooting it down
+        if rammed {
+            self.destroy_saucer(true);
+        }
+        if shot.is_some() || rammed {
+            self.strike_ship();
+        }
+    }
+
+    // the shield takes the blow while it's up
+    fn strike_ship(&mut self) {
+        if self.player.shielding {
+            let player = &mut self.player;
+            player.shield_energy = (player.shield_energy - self.config.shield.hit_cost).max(0.0);
+            self.events.push(WorldEvent::ShieldHit);
+        } else {
+            self.hit_player();
+        }
+    }
+
+    // the player's shots against the saucer
+    fn shoot_saucer(&mut self) {
+        let Some(saucer) = &self.saucer else {
+            return;
+        };
+        let polygon = self.mode.polygon_collisions;
+        let outline = polygon.then(|| saucer.world_outline());
+        let hit = self.projectiles.iter().position(|p| {
+            let (radius, segment) = projectile_hitbox(p, &self.shapes.projectile, polygon);
+            !p.pierced.contains(&saucer.id)
+                && check_collision(p.pos, radius, saucer.pos, saucer.radius)
+                && segment
+                    .zip(outline.as_ref())
+                    .is_none_or(|(segment, outline)| outlines_intersect(&segment, outline))
+        });
+        if let Some(pi) = hit {
+            let projectile = &mut self.projectiles[pi];
+            if projectile.hits == 0 {
+                self.stats.shots_hit += 1;
+            }
+            projectile.hits += 1;
+            projectile.pierced.push(saucer.id);
+            if projectile.hits >= projectile.damage {
+                self.projectiles.remove(pi);
+            }
+            self.destroy_saucer(true);
+        }
+    }
+
+    // a saucer flying into an asteroid takes it down with it, nobody scores
+    fn collide_saucer_with_asteroids(&mut self) {
+        let Some(saucer) = &self.saucer else {
+            return;
+        };
+        let outline = self.mode.polygon_collisions.then(|| saucer.world_outline());
+        self.grid
+            .query(saucer.pos, saucer.radius, &mut self.candidates);
+        let hit = self.candidates.iter().copied().find(|&ai| {
+            let asteroid = &self.asteroids[ai];
+            check_collision(saucer.pos, saucer.radius, asteroid.pos, asteroid.radius)
+                && outline
+                    .as_ref()
+                    .is_none_or(|outline| outlines_intersect(outline, &asteroid.world_outline()))
+        });
+        let Some(ai) = hit else {
+            return;
+        };
+
+        let asteroid = self.asteroids.remove(ai);
+        let speed_scale = self.speed_scale();
+        let shapes = shape_source(&self.mode, &self.shapes, &self.config);
+        let children = split_asteroid(&asteroid, &mut self.rng, &shapes, &self.config.asteroids);
+        self.asteroids.extend(children.into_iter().map(|mut child| {
+            child.vel.0 *= speed_scale;
+            child.vel.1 *= speed_scale;
+            child.id = self.next_id;
+            self.next_id += 1;
+            child
+        }));
+        self.events.push(WorldEvent::AsteroidDestroyed);
+        self.destroy_saucer(false);
+    }
+
+    // the next one shows up `interval` after this one is gone
+    fn destroy_saucer(&mut self, by_player: bool) {
+        let Some(saucer) = self.saucer.take() else {
+            return;
+        };
+        if by_player {
+            self.player.score +=
+                self.config.saucers.size(saucer.size).score * self.score_multiplier();
+            self.stats.saucers_destroyed += 1;
+        }
+        self.events.push(WorldEvent::SaucerDestroyed);
+        self.next_saucer_at = self.time + self.config.saucers.interval();
+    }
+
+    // across the screen, changing course now and then, wrapping top to bottom
+    fn move_saucer(&mut self, dt: f32) {
+        let Some(saucer) = &mut self.saucer else {
+            return;
+        };
+        let config = &self.config.saucers;
+        if self.time >= saucer.next_turn {
+            saucer.turn(config, &mut self.rng);
+            saucer.next_turn = self.time + config.turn_interval();
+        }
+        saucer.pos.0 += saucer.vel.0 * dt;
+        saucer.pos.1 += saucer.vel.1 * dt;
+        if saucer.has_left(self.viewport) {
+            self.saucer = None;
+            self.next_saucer_at = self.time + config.interval();
+        } else {
+            wrap_position(&mut saucer.pos, self.viewport, saucer.radius);
+        }
+    }
+
+    // send in the next saucer once it's due, and keep the current one shooting
+    fn update_saucer(&mut self) {
+        let config = &self.config.saucers;
+        match &mut self.saucer {
+            None if config.enabled && self.time >= self.next_saucer_at => {
+                let size = pick_saucer_size(&mut self.rng, self.player.score, config);
+                let saucer = spawn_saucer(
+                    &self.shapes.saucer,
+                    size,
+                    self.viewport,
+                    self.time,
+                    config,
+                    &mut self.rng,
+                );
+                self.saucer = Some(Saucer {
+                    id: self.next_id,
+                    ..saucer
+                });
+                self.next_id += 1;
+            }
+            Some(saucer) if self.time >= saucer.next_shot => {
+                saucer.next_shot = self.time + config.fire_interval();
+                // large saucers fire anywhere, small ones at the ship, getting
+                // better at it as the score goes up
+                let player = &self.player;
+                let angle = match saucer.size {
+                    SaucerSize::Small if player.is_flying() => {
+                        let target = if config.lead_targeting {
+                            lead_target(
+                                saucer.pos,
+                                player.pos,
+                                player.vel,
+                                config.projectile_speed,
+                                self.viewport,
+                                PLAYER_MARGIN,
+                            )
+                        } else {
+                            player.pos
+                        };
+                        let error = aim_error(&mut self.rng, config.aim_error(player.score));
+                        aim_angle(saucer.pos, target) + error
+                    }
+                    _ => self.rng.gen_range(0.0..TAU),
+                };
+                let dir = heading(angle);
+                let speed = config.projectile_speed;
+                self.saucer_projectiles.push(Projectile {
+                    pos: saucer.pos,
+                    vel: (speed * dir.0, speed * dir.1),
+                    angle,
+                    radius: PROJECTILE_RADIUS,
+                    prev_pos: saucer.pos,
+                    damage: 1,
+                    hits: 0,
+                    pierced: Vec::new(),
+                });
+                self.events.push(WorldEvent::SaucerShot);
+            }
+            _ => {}
+        }
+    }
+
+    fn has_effect(&self, kind: PowerUpKind) -> bool {
+        self.effects.iter().any(|&(active, _)| active == kind)
+    }
+
+    fn score_multiplier(&self) -> i32 {
+        if self.has_effect(PowerUpKind::ScoreMultiplier) {
+            self.config.powerups.multiplier
+        } else {
+            1
+        }
+    }
+
+    // drift and wrap like the asteroids, gone once they've been left too long
+    fn move_powerups(&mut self, dt: f32) {
+        let config = &self.config.powerups;
+        for powerup in self.powerups.iter_mut() {
+            powerup.pos.0 += powerup.vel.0 * dt;
+            powerup.pos.1 += powerup.vel.1 * dt;
+            wrap_position(&mut powerup.pos, self.viewport, config.radius);
+        }
+        let lifetime = config.lifetime();
+        self.powerups
+            .retain(|powerup| self.time - powerup.dropped < lifetime);
+    }
+
+    fn collect_powerups(&mut self) {
+        if !self.player.is_flying() {
+            return;
+        }
+        let (pos, radius) = (self.player.pos, self.ship_radius());
+        let config = &self.config.powerups;
+        let mut collected = Vec::new();
+        self.powerups.retain(|powerup| {
+            let touching = check_collision(pos, radius, powerup.pos, config.radius);
+            if touching {
+                collected.push(powerup.kind);
+            }
+            !touching
+        });
+        for kind in collected {
+            self.apply_powerup(kind);
+        }
+    }
+
+    fn apply_powerup(&mut self, kind: PowerUpKind) {
+        if kind.is_timed() {
+            // another of the same kind starts the clock over
+            let until = self.time + self.config.powerups.duration();
+            self.effects.retain(|&(active, _)| active != kind);
+            self.effects.push((kind, until));
+        }
+        match kind {
+            PowerUpKind::RapidFire => self.weapon = WeaponKind::Rapid,
+            PowerUpKind::Spread => self.weapon = WeaponKind::Spread,
+            PowerUpKind::ShieldRecharge => self.player.shield_energy = 1.0,
+            PowerUpKind::ExtraLife if self.player.health < self.config.lives.max => {
+                self.player.health += 1;
+                self.last_extra_life = Some(self.time);
+                self.events.push(WorldEvent::ExtraLife);
+                return;
+            }
+            _ => {}
+        }
+        self.events.push(WorldEvent::PowerUp);
+    }
+
+    fn hit_player(&mut self) {
+        self.player.health -= 1;
+        if self.config.ship.explode_on_hit {
+            self.player.died_at = Some(self.time);
+            self.player.vel = (0.0, 0.0);
+        } else {
+            self.player.last_hit = Some(self.time);
+        }
+        self.events.push(WorldEvent::PlayerHit);
+    }
+
+    // vanish now, the ship is already at the destination while it fades back in
+    fn start_jump(&mut self) {
+        let margin = self.config.hyperspace.margin;
+        let (w, h) = (self.viewport.0 as f32, self.viewport.1 as f32);
+        // a viewport smaller than the margins just jumps to the middle
+        let pick = |rng: &mut StdRng, size: f32| {
+            if size > 2.0 * margin {
+                rng.gen_range(margin..size - margin)
+            } else {
+                size / 2.0
+            }
+        };
+        let to = (pick(&mut self.rng, w), pick(&mut self.rng, h));
+        let player = &mut self.player;
+        player.jump = Some((player.pos, self.time));
+        player.last_jump = Some(self.time);
+        player.pos = to;
+        player.prev_pos = to;
+        player.vel = (0.0, 0.0);
+        self.events.push(WorldEvent::Hyperspace);
+    }
+
+    // re-entry, with the odd bad landing like the arcade original
+    fn update_jump(&mut self) {
+        let Some((_, started)) = self.player.jump else {
+            return;
+        };
+        if self.time - started < self.config.hyperspace.duration() {
+            return;
+        }
+        self.player.jump = None;
+        if self.rng.gen::<f32>() < self.config.hyperspace.self_destruct_chance {
+            self.hit_player();
+        }
+    }
+
+    fn respawn_zone_clear(&self) -> bool {
+        let center = (self.viewport.0 as f32 / 2.0, self.viewport.1 as f32 / 2.0);
+        let safe_radius = self.config.ship.safe_radius;
+        !self
+            .asteroids
+            .iter()
+            .any(|a| check_collision(center, safe_radius, a.pos, a.radius))
+    }
+
+    // back to the centre once the wait is over and nothing is in the way
+    fn try_respawn(&mut self) {
+        let waited = self
+            .since_death()
+            .is_some_and(|since| since >= self.config.ship.respawn_delay());
+        if self.player.health <= 0 || !waited || !self.respawn_zone_clear() {
+            return;
+        }
+        let player = &mut self.player;
+        player.pos = (self.viewport.0 as f32 / 2.0, self.viewport.1 as f32 / 2.0);
+        player.prev_pos = player.pos;
+        player.vel = (0.0, 0.0);
+        player.angle = 0.0;
+        player.prev_angle = 0.0;
+        player.died_at = None;
+        // a moment of grace in case something flies in right away
+        player.last_hit = Some(self.time);
+    }
+
+    // one life per threshold crossed, anything over the cap is lost
+    fn award_extra_lives(&mut self) {
+        let lives = &self.config.lives;
+        while let Some(threshold) = self.next_extra_life {
+            if self.player.score < threshold {
+                break;
+            }
+            self.next_extra_life = lives.next_threshold(threshold);
+            if self.player.health < lives.max {
+                self.player.health += 1;
+                self.last_extra_life = Some(self.time);
+                self.events.push(WorldEvent::ExtraLife);
+            }
+        }
+    }
+
+    // later waves are faster, endless mode never speeds up
+    fn speed_scale(&self) -> f32 {
+        if self.mode.endless {
+            1.0
+        } else {
+            self.config.waves.speed_scale(self.wave)
+        }
+    }
+
+    fn fill_asteroids(&mut self) {
+        while self.asteroids.len() < self.config.asteroids.starting_count {
+            self.spawn_large();
+        }
+    }
+
+    // wait a moment once the field is clear, then send in the next wave
+    fn update_waves(&mut self) {
+        match self.next_wave_at {
+            Some(at) if self.time >= at => {
+                self.next_wave_at = None;
+                self.wave += 1;
+                for _ in 0..self.config.waves.count(self.wave) {
+                    self.spawn_large();
+                }
+            }
+            Some(_) => {}
+            None if self.asteroids.is_empty() => {
+                self.next_wave_at = Some(self.time + self.config.waves.delay());
+            }
+            None => {}
+        }
+    }
+
+    // a large asteroid just off screen, drifting in
+    fn spawn_large(&mut self) {
+        let speed_scale = self.speed_scale();
+        let config = &self.config.asteroids;
+        let base = shape_source(&self.mode, &self.shapes, &self.config).pick(&mut self.rng);
+        let pos = pick_spawn_point(&mut self.rng, self.viewport, config.margin);
+        let speed_range = config.large.speed_range.clone();
+        let vel = pick_random_velocity(&mut self.rng, speed_range);
+        let vel = (vel.0 * speed_scale, vel.1 * speed_scale);
+        let angle = self.rng.gen_range(0.0..TAU);
+        let asteroid = spawn_asteroid(
+            &base,
+            AsteroidSize::Large,
+            pos,
+            vel,
+            angle,
+            config,
+            &mut self.rng,
+        );
+        self.asteroids.push(Asteroid {
+            id: self.next_id,
+            ..asteroid
+        });
+        self.next_id += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const VIEWPORT: (u32, u32) = (800, 600);
+
+    fn shapes() -> Shapes {
+        Shapes::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/shapes.toml")).unwrap()
+    }
+
+    // wave mode, so the field stays empty until the test puts something in it
+    fn empty_world() -> World {
+        empty_world_with(GameConfig::default())
+    }
+
+    fn empty_world_with(config: GameConfig) -> World {
+        World::new(VIEWPORT, 7, GameMode::default(), shapes(), config)
+    }
+
+    fn place_asteroid(world: &mut World, size: AsteroidSize, pos: Vec2) {
+        let mut rng = StdRng::seed_from_u64(0);
+        let base = world.shapes.asteroids[0].clone();
+        let asteroid = spawn_asteroid(
+            &base,
+            size,
+            pos,
+            (0.0, 0.0),
+            0.0,
+            &world.config.asteroids,
+            &mut rng,
+        );
+        world.asteroids.push(Asteroid {
+            id: world.next_id,
+            ..asteroid
+        });
+        world.next_id += 1;
+    }
+
+    #[test]
+    fn a_hit_costs_a_life() {
+        let mut world = empty_world();
+        let starting = world.player.health;
+        let pos = world.player.pos;
+        place_asteroid(&mut world, AsteroidSize::Large, pos);
+
+        world.step(&InputState::default());
+
+        assert_eq!(world.player.health, starting - 1);
+        assert!(world.drain_events().any(|e| e == WorldEvent::PlayerHit));
+        assert!(!world.player.is_alive());
+    }
+
+    #[test]
+    fn respawn_waits_for_the_delay_and_a_clear_centre() {
+        let mut world = empty_world();
+        let centre = world.player.pos;
+        place_asteroid(&mut world, AsteroidSize::Large, centre);
+        world.step(&InputState::default());
+        assert!(!world.player.is_alive());
+
+        // still exploding, then waiting on the asteroid in the way
+        let delay_ticks = world.config.ship.respawn_delay().as_nanos() / TICK.as_nanos();
+        for _ in 1..delay_ticks {
+            world.step(&InputState::default());
+            assert!(!world.respawn_blocked());
+        }
+        for _ in 0..TICK_RATE {
+            world.step(&InputState::default());
+        }
+        assert!(world.respawn_blocked());
+        assert!(!world.player.is_alive());
+
+        world.asteroids[0].pos = (50.0, 50.0);
+        world.step(&InputState::default());
+        assert!(world.player.is_alive());
+        assert_eq!(world.player.pos, centre);
+        assert!(world.invulnerable_elapsed().is_some());
+    }
+
+    #[test]
+    fn a_shot_asteroid_splits_into_children() {
+        let mut world = empty_world();
+        // straight ahead of the ship, which starts in the middle facing up
+        let (x, y) = world.player.pos;
+        place_asteroid(&mut world, AsteroidSize::Large, (x, y - 150.0));
+
+        let fire = InputState {
+            fire: true,
+            ..InputState::default()
+        };
+        world.step(&fire);
+        for _ in 0..TICK_RATE {
+            if world.stats.large_destroyed > 0 {
+                break;
+            }
+            world.step(&InputState::default());
+        }
+
+        let config = &world.config.asteroids;
+        assert_eq!(world.stats.large_destroyed, 1);
+        assert_eq!(world.asteroids.len(), config.large.children);
+        assert!(world
+            .asteroids
+            .iter()
+            .all(|a| a.size == AsteroidSize::Medium && a.pos == (x, y - 150.0)));
+        assert_eq!(world.player.score, config.large.score);
+        assert!(world.projectiles.is_empty());
+    }
+
+    #[test]
+    fn piercing_shots_go_through_instead_of_into_the_fragments() {
+        let mut world = empty_world();
+        let (x, y) = world.player.pos;
+        place_asteroid(&mut world, AsteroidSize::Large, (x, y - 100.0));
+        place_asteroid(&mut world, AsteroidSize::Large, (x, y - 220.0));
+        world.switch_weapon();
+        assert_eq!(world.weapon(), WeaponKind::Piercing);
+
+        let fire = InputState {
+            fire: true,
+            ..InputState::default()
+        };
+        world.step(&fire);
+        for _ in 0..TICK_RATE {
+            world.step(&InputState::default());
+        }
+
+        assert_eq!(world.stats.large_destroyed, 2);
+        assert_eq!(world.stats.medium_destroyed, 0);
+        assert_eq!(world.stats.shots_hit, 1);
+        assert!(world.projectiles.is_empty());
+    }
+
+    fn extra_lives_config(starting: i32, max: i32, every: i32) -> GameConfig {
+        let mut config = GameConfig::default();
+        config.lives.starting = starting;
+        config.lives.max = max;
+        config.lives.extra_life_first = 1000;
+        config.lives.extra_life_every = every;
+        config
+    }
+
+    fn extra_lives_awarded(world: &mut World) -> usize {
+        world.step(&InputState::default());
+        world
+            .drain_events()
+            .filter(|&e| e == WorldEvent::ExtraLife)
+            .count()
+    }
+
+    #[test]
+    fn crossing_two_thresholds_at_once_gives_two_lives() {
+        let mut world = empty_world_with(extra_lives_config(2, 5, 1000));
+        world.player.score = 2500;
+        assert_eq!(extra_lives_awarded(&mut world), 2);
+        assert_eq!(world.player.health, 4);
+        assert_eq!(world.next_extra_life, Some(3000));
+    }
+
+    #[test]
+    fn extra_lives_over_the_cap_are_lost() {
+        let mut world = empty_world_with(extra_lives_config(4, 5, 1000));
+        world.player.score = 3500;
+        assert_eq!(extra_lives_awarded(&mut world), 1);
+        assert_eq!(world.player.health, 5);
+        // the thresholds passed at the cap don't come back later
+        assert_eq!(world.next_extra_life, Some(4000));
+        world.player.health = 3;
+        assert_eq!(extra_lives_awarded(&mut world), 0);
+    }
+
+    #[test]
+    fn no_repeat_means_a_single_extra_life() {
+        let mut world = empty_world_with(extra_lives_config(2, 5, 0));
+        world.player.score = 1000;
+        assert_eq!(extra_lives_awarded(&mut world), 1);
+        assert_eq!(world.next_extra_life, None);
+        world.player.score = 50_000;
+        assert_eq!(extra_lives_awarded(&mut world), 0);
+        assert_eq!(world.player.health, 3);
+    }
+
+    #[test]
+    fn piercing_shots_break_sibling_fragments_in_a_row() {
+        let mut world = empty_world();
+        let (x, y) = world.player.pos;
+        place_asteroid(&mut world, AsteroidSize::Large, (x, y - 100.0));
+        let parent = world.asteroids.pop().unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+        let shapes = ShapeSource::Outlines(&world.shapes.asteroids);
+        let children = split_asteroid(&parent, &mut rng, &shapes, &world.config.asteroids);
+        let mut siblings = Vec::new();
+        for (child, dy) in children.into_iter().zip([100.0, 220.0]) {
+            siblings.push(Asteroid {
+                pos: (x, y - dy),
+                vel: (0.0, 0.0),
+                id: world.next_id,
+                ..child
+            });
+            world.next_id += 1;
+        }
+        world.asteroids = siblings;
+        world.switch_weapon();
+
+        let fire = InputState {
+            fire: true,
+            ..InputState::default()
+        };
+        world.step(&fire);
+        for _ in 0..TICK_RATE {
+            world.step(&InputState::default());
+        }
+
+        assert_eq!(world.stats.medium_destroyed, 2);
+        assert_eq!(world.stats.small_destroyed, 0);
+    }
+
+    #[test]
+    fn same_seed_and_inputs_give_the_same_game() {
+        let mode = GameMode {
+            endless: true,
+            ..GameMode::default()
+        };
+        let mut a = World::new(VIEWPORT, 42, mode, shapes(), GameConfig::default());
+        let mut b = World::new(VIEWPORT, 42, mode, shapes(), GameConfig::default());
+        for tick in 0..600 {
+            let input = InputState {
+                thrust: tick % 90 < 30,
+                turn_left: tick % 50 < 10,
+                fire: tick % 20 == 0,
+                ..InputState::default()
+            };
+            a.step(&input);
+            b.step(&input);
+        }
+        assert_eq!(a.player.pos, b.player.pos);
+        assert_eq!(a.player.score, b.player.score);
+        assert_eq!(a.stats, b.stats);
+        let positions = |w: &World| w.asteroids.iter().map(|a| a.pos).collect::<Vec<_>>();
+        assert_eq!(positions(&a), positions(&b));
+    }
+}